@@ -1,5 +1,179 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
 mod test;
 
+/// The traits and types that generated code implements and returns: `use optionalize_core::prelude::*;`.
+pub mod prelude {
+    pub use crate::{
+        FieldSet, FieldState, FieldUnset, MissingFieldError, OptimisticLockError, OptionalizeMerge, OptionalizeTrait,
+    };
+
+    #[cfg(feature = "std")]
+    pub use crate::{GenericOptional, MissingFieldsError};
+}
+
 pub trait OptionalizeTrait {
-    type Optional;
+    /// The generated struct with every non-ignored field wrapped in `Option`.
+    ///
+    /// Every generated Optional implements `Default` (all fields `None`) and `Clone`,
+    /// so generic code can start from an empty value:
+    ///
+    /// ```rust
+    /// use optionalize_core::OptionalizeTrait;
+    ///
+    /// fn empty_optional<T: OptionalizeTrait>() -> T::Optional {
+    ///     Default::default()
+    /// }
+    /// ```
+    type Optional: Default + Clone;
+}
+
+/// Combines two optionalized values into one.
+///
+/// Fields that are set on `self` win; fields left as `None` are taken from `rhs`.
+/// The `Optionalize` derive implements this for every generated `Optional` struct,
+/// so generic code can merge patches without knowing the concrete type:
+///
+/// ```rust
+/// use optionalize_core::OptionalizeMerge;
+///
+/// fn apply_defaults<O: OptionalizeMerge>(user_patch: O, defaults: O) -> O {
+///     user_patch.merge(defaults)
+/// }
+/// ```
+pub trait OptionalizeMerge<Rhs = Self> {
+    fn merge(self, rhs: Rhs) -> Self;
+}
+
+/// State of a required field in a `#[optionalize(typestate)]` builder.
+pub trait FieldState {}
+
+/// The required field has been given a value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldSet;
+
+/// The required field has not been given a value yet.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldUnset;
+
+impl FieldState for FieldSet {}
+impl FieldState for FieldUnset {}
+
+/// Returned when converting an optionalized value back into the original struct
+/// and a required field was never set.
+///
+/// With the `serde` feature it serializes as `{"field": "..."}` (deserializing borrows the
+/// name, so the input must be `'static`); with the `axum` feature it is also an
+/// `IntoResponse` answering `422 Unprocessable Entity` with that body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MissingFieldError {
+    pub field: &'static str,
+}
+
+impl core::fmt::Display for MissingFieldError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Missing required field: {}", self.field)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MissingFieldError {}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for MissingFieldError {
+    fn into_response(self) -> axum::response::Response {
+        (axum::http::StatusCode::UNPROCESSABLE_ENTITY, axum::Json(self)).into_response()
+    }
+}
+
+/// Returned by the `find_and_check_version()` that `#[optionalize(sea_orm_version_column)]`
+/// generates; `E` is the database error type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptimisticLockError<E> {
+    /// The row's version is not the expected one: someone else updated it first.
+    VersionMismatch { expected: i64, found: i64 },
+    /// No row has the given primary key.
+    NotFound,
+    /// The query itself failed.
+    Database(E),
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for OptimisticLockError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OptimisticLockError::VersionMismatch { expected, found } => {
+                write!(f, "Version mismatch: expected {}, found {}", expected, found)
+            }
+            OptimisticLockError::NotFound => write!(f, "Row not found"),
+            OptimisticLockError::Database(err) => write!(f, "Database error: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for OptimisticLockError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OptimisticLockError::Database(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Returned by `try_complete()` under `#[optionalize(accumulate_errors)]`, listing
+/// every required field that was never set. Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MissingFieldsError {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub fields: Vec<&'static str>,
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for MissingFieldsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Missing required fields: {}", self.fields.join(", "))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MissingFieldsError {}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for MissingFieldsError {
+    fn into_response(self) -> axum::response::Response {
+        (axum::http::StatusCode::UNPROCESSABLE_ENTITY, axum::Json(self)).into_response()
+    }
+}
+
+/// Field-name keyed view of an optionalized value: only the fields that are set have
+/// an entry. `#[optionalize(generic_optional)]` generates `into_generic()`, producing a
+/// `GenericOptional<serde_json::Value>`. Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericOptional<T> {
+    pub fields: std::collections::HashMap<String, T>,
+}
+
+#[cfg(feature = "std")]
+impl<T> GenericOptional<T> {
+    /// The value of `field`, or `None` when it is not set.
+    pub fn get(&self, field: &str) -> Option<&T> {
+        self.fields.get(field)
+    }
+
+    /// Whether `field` is set.
+    pub fn is_set(&self, field: &str) -> bool {
+        self.fields.contains_key(field)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for GenericOptional<T> {
+    fn default() -> Self {
+        GenericOptional { fields: std::collections::HashMap::new() }
+    }
 }
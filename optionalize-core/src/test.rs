@@ -1,11 +1,12 @@
 
-use super::OptionalizeTrait;
+use super::{MissingFieldError, OptimisticLockError, OptionalizeMerge, OptionalizeTrait};
 
 struct TestStruct {
     pub id: i32,
     pub name: String,
 }
 
+#[derive(Default, Clone)]
 struct TestStructOptional {
     pub id: Option<i32>,
     pub name: Option<String>,
@@ -31,3 +32,73 @@ fn test_optionalize_trait() {
     assert_eq!(optionalized.id, Some(1));
     assert_eq!(optionalized.name, Some("example".to_string()));
 }
+
+#[test]
+fn test_optional_default() {
+    fn empty_optional<T: OptionalizeTrait>() -> T::Optional {
+        Default::default()
+    }
+
+    let empty = empty_optional::<TestStruct>();
+    assert_eq!(empty.id, None);
+    assert_eq!(empty.name, None);
+}
+
+impl OptionalizeMerge for TestStructOptional {
+    fn merge(self, rhs: Self) -> Self {
+        TestStructOptional {
+            id: self.id.or(rhs.id),
+            name: self.name.or(rhs.name),
+        }
+    }
+}
+
+#[test]
+fn test_optionalize_merge_trait() {
+    fn apply_defaults<O: OptionalizeMerge>(user_patch: O, defaults: O) -> O {
+        user_patch.merge(defaults)
+    }
+
+    let user_patch = TestStructOptional {
+        id: None,
+        name: Some("patched".to_string()),
+    };
+    let defaults = TestStructOptional {
+        id: Some(7),
+        name: Some("default".to_string()),
+    };
+
+    let merged = apply_defaults(user_patch, defaults);
+    assert_eq!(merged.id, Some(7));
+    assert_eq!(merged.name, Some("patched".to_string()));
+}
+
+#[test]
+fn test_missing_field_error_display() {
+    assert_eq!(MissingFieldError { field: "name" }.to_string(), "Missing required field: name");
+}
+
+#[test]
+fn test_optimistic_lock_error_display() {
+    let error = OptimisticLockError::<&str>::VersionMismatch { expected: 1, found: 2 };
+    assert_eq!(error.to_string(), "Version mismatch: expected 1, found 2");
+    assert_eq!(OptimisticLockError::Database("timeout").to_string(), "Database error: timeout");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_missing_field_error_serde() {
+    let error = MissingFieldError { field: "name" };
+    let json = serde_json::to_string(&error).unwrap();
+    assert_eq!(json, r#"{"field":"name"}"#);
+    assert_eq!(serde_json::from_str::<MissingFieldError>(r#"{"field":"name"}"#).unwrap(), error);
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn test_missing_field_error_into_response() {
+    use axum::response::IntoResponse;
+
+    let response = MissingFieldError { field: "name" }.into_response();
+    assert_eq!(response.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,2 @@
+//! End-to-end tests of the `Optionalize` SeaORM integration against an in-memory SQLite
+//! database. Everything lives in `tests/`; this crate has no library code.
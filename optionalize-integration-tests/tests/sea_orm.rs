@@ -0,0 +1,556 @@
+use optionalize::{MissingFieldError, OptimisticLockError, Optionalize};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, Database, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, Schema, Set,
+    TransactionTrait,
+};
+
+mod user {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Optionalize)]
+    #[sea_orm(table_name = "users")]
+    #[optionalize(
+        sea_orm_find_by,
+        sea_orm_pagination,
+        sea_orm_with_transaction,
+        sea_orm_find_or_create,
+        sea_orm_batch_update,
+        sea_orm_preload(super::post::Entity)
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub name: String,
+        pub email: Option<String>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(has_many = "super::post::Entity")]
+        Post,
+    }
+
+    impl Related<super::post::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Post.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod post {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "posts")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub user_id: i32,
+        pub title: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(belongs_to = "super::user::Entity", from = "Column::UserId", to = "super::user::Column::Id")]
+        User,
+    }
+
+    impl Related<super::user::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::User.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod note {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+    use sea_orm::{ActiveValue, Set};
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Optionalize)]
+    #[sea_orm(table_name = "notes")]
+    #[optionalize(
+        sea_orm_active_model_behavior,
+        sea_orm_find_by,
+        sea_orm_exclusive_lock,
+        sea_orm_returning,
+        sea_orm_with_transaction,
+        sea_orm_batch_update,
+        sea_orm_find_or_create,
+        sea_orm_default_values
+    )]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub title: String,
+        /// Written by `before_save`: how the hook was told the row is being saved.
+        #[optionalize(skip, sea_orm_not_set_default)]
+        pub saved_as: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    #[async_trait::async_trait]
+    impl ActiveModelBehavior for ActiveModel {
+        async fn before_save<C: ConnectionTrait>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr> {
+            if let ActiveValue::Set(title) = &self.title {
+                if insert {
+                    self.title = Set(title.trim().to_string());
+                }
+            }
+            self.saved_as = Set(if insert { "insert" } else { "update" }.to_string());
+            Ok(self)
+        }
+    }
+}
+
+mod document {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Optionalize)]
+    #[sea_orm(table_name = "documents")]
+    #[optionalize(sea_orm_prelude, sea_orm_version_column = "version")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub body: String,
+        pub version: i32,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod article {
+    use super::*;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Optionalize)]
+    #[sea_orm(table_name = "articles")]
+    #[optionalize(sea_orm_soft_delete = "deleted_at", sea_orm_soft_undelete = "deleted_at", sea_orm_timestamp = "updated_at")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        #[optionalize(skip)]
+        pub id: i32,
+        pub title: String,
+        pub updated_at: DateTime,
+        pub deleted_at: Option<DateTime>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod line_item {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "line_items")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: i32,
+        pub quantity: u32,
+        pub offset: Option<i64>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+// SeaORM has no column type for `NonZero*`, so the entity stores plain integers.
+mod line_item_patch {
+    use super::*;
+    use super::line_item::{Column, Entity};
+    use std::num::{NonZeroI64, NonZeroU32};
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_active_model_type = "super::line_item::ActiveModel", sea_orm_find_by)]
+    pub struct LineItem {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub quantity: NonZeroU32,
+        pub offset: Option<NonZeroI64>,
+    }
+}
+
+// Inserts leave the auto-increment key to the database.
+mod post_draft {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_active_model_type = "super::post::ActiveModel", sea_orm_default_values)]
+    pub struct Post {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub user_id: i32,
+        pub title: String,
+    }
+}
+
+async fn setup() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    let backend = db.get_database_backend();
+    for statement in [
+        Schema::new(backend).create_table_from_entity(user::Entity),
+        Schema::new(backend).create_table_from_entity(post::Entity),
+        Schema::new(backend).create_table_from_entity(note::Entity),
+        Schema::new(backend).create_table_from_entity(document::Entity),
+        Schema::new(backend).create_table_from_entity(line_item::Entity),
+        Schema::new(backend).create_table_from_entity(article::Entity),
+    ] {
+        db.execute(backend.build(&statement)).await.unwrap();
+    }
+    db
+}
+
+async fn insert(db: &DatabaseConnection, id: i32, name: &str, email: Option<&str>) -> user::Model {
+    let optional = user::ModelOptional {
+        id,
+        name: Some(name.to_string()),
+        email: email.map(str::to_string),
+    };
+    optional.to_active().insert(db).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_to_active_inserts_set_fields() {
+    let db = setup().await;
+    let inserted = insert(&db, 1, "Ada", None).await;
+    assert_eq!(inserted, user::Model { id: 1, name: "Ada".to_string(), email: None });
+}
+
+#[tokio::test]
+async fn test_to_active_updates_only_set_fields() {
+    let db = setup().await;
+    insert(&db, 1, "Ada", Some("ada@example.com")).await;
+
+    let patch = user::ModelOptional { id: 1, name: Some("Ada Lovelace".to_string()), email: None };
+    patch.to_active().update(&db).await.unwrap();
+
+    let stored = user::Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+    assert_eq!(stored.name, "Ada Lovelace");
+    assert_eq!(stored.email, Some("ada@example.com".to_string()));
+}
+
+#[tokio::test]
+async fn test_try_from_round_trips_a_stored_row() {
+    let db = setup().await;
+    let inserted = insert(&db, 7, "Grace", Some("grace@example.com")).await;
+
+    let stored = user::Entity::find_by_id(7).one(&db).await.unwrap().unwrap();
+    let optional = user::ModelOptional::from(stored);
+    assert_eq!(user::Model::try_from(optional), Ok(inserted));
+    assert_eq!(
+        user::Model::try_from(user::ModelOptional { id: 7, name: None, email: None }),
+        Err(MissingFieldError { field: "name" })
+    );
+}
+
+#[tokio::test]
+async fn test_patch_and_save() {
+    let db = setup().await;
+    let stored = insert(&db, 3, "Linus", None).await;
+
+    let patch = user::ModelOptional { id: 3, name: None, email: Some("linus@example.com".to_string()) };
+    let patched = patch.patch(stored);
+    user::ModelOptional::from(patched.clone()).to_active().update(&db).await.unwrap();
+
+    let reloaded = user::Entity::find_by_id(3).one(&db).await.unwrap().unwrap();
+    assert_eq!(reloaded, patched);
+    assert_eq!(reloaded.email, Some("linus@example.com".to_string()));
+}
+
+#[tokio::test]
+async fn test_find_by_filters_on_set_fields() {
+    let db = setup().await;
+    let ada = insert(&db, 1, "Ada", Some("ada@example.com")).await;
+    let other_ada = insert(&db, 2, "Ada", None).await;
+    insert(&db, 3, "Grace", None).await;
+
+    let by_name = user::ModelOptional { id: 0, name: Some("Ada".to_string()), email: None };
+    assert_eq!(by_name.find_by(&db).await.unwrap(), vec![ada.clone(), other_ada]);
+
+    let by_email = user::ModelOptional { id: 0, name: None, email: Some("ada@example.com".to_string()) };
+    assert_eq!(by_email.find_by(&db).await.unwrap(), vec![ada]);
+}
+
+#[tokio::test]
+async fn test_to_active_runs_before_save() {
+    let db = setup().await;
+    let optional = note::ModelOptional { id: 1, title: Some("  draft  ".to_string()), saved_as: String::new() };
+    let inserted = optional.to_active(&db).await.unwrap().insert(&db).await.unwrap();
+    assert_eq!(inserted, note::Model { id: 1, title: "draft".to_string(), saved_as: "insert".to_string() });
+}
+
+#[tokio::test]
+async fn test_paginate_filters_and_pages() {
+    let db = setup().await;
+    for id in 1..=5 {
+        insert(&db, id, "Ada", None).await;
+    }
+    insert(&db, 6, "Grace", None).await;
+
+    let filter = || user::ModelOptional { id: 0, name: Some("Ada".to_string()), email: None };
+    let first = user::ModelOptional::paginate(&db, filter(), 0, 2).await.unwrap();
+    assert_eq!(first.iter().map(|user| user.id).collect::<Vec<_>>(), vec![1, 2]);
+    let last = user::ModelOptional::paginate(&db, filter(), 2, 2).await.unwrap();
+    assert_eq!(last.iter().map(|user| user.id).collect::<Vec<_>>(), vec![5]);
+}
+
+#[tokio::test]
+async fn test_save_in_transaction() {
+    let db = setup().await;
+    insert(&db, 1, "Ada", None).await;
+
+    let patch = user::ModelOptional { id: 1, name: None, email: Some("ada@example.com".to_string()) };
+    let saved = patch.save(&db).await.unwrap();
+    assert_eq!(saved, user::Model { id: 1, name: "Ada".to_string(), email: Some("ada@example.com".to_string()) });
+    assert_eq!(user::Entity::find_by_id(1).one(&db).await.unwrap(), Some(saved));
+}
+
+#[tokio::test]
+async fn test_find_or_create() {
+    let db = setup().await;
+    let existing = insert(&db, 1, "Ada", None).await;
+    let defaults = || user::ModelOptional { id: 0, name: Some("Guest".to_string()), email: None };
+
+    assert_eq!(user::ModelOptional::find_or_create(&db, 1, defaults()).await.unwrap(), (existing, false));
+    let (created, was_created) = user::ModelOptional::find_or_create(&db, 2, defaults()).await.unwrap();
+    assert!(was_created);
+    assert_eq!(created, user::Model { id: 2, name: "Guest".to_string(), email: None });
+}
+
+#[tokio::test]
+async fn test_batch_update() {
+    let db = setup().await;
+    for (id, name) in [(1, "Ada"), (2, "Grace"), (3, "Linus")] {
+        insert(&db, id, name, None).await;
+    }
+
+    let patch = user::ModelOptional { id: 0, name: None, email: Some("team@example.com".to_string()) };
+    assert_eq!(user::ModelOptional::batch_update(&db, vec![1, 3], patch).await.unwrap(), 2);
+    let users = user::Entity::find().all(&db).await.unwrap();
+    let emails: Vec<_> = users.iter().map(|user| (user.name.as_str(), user.email.as_deref())).collect();
+    assert_eq!(
+        emails,
+        vec![("Ada", Some("team@example.com")), ("Grace", None), ("Linus", Some("team@example.com"))]
+    );
+}
+
+#[tokio::test]
+async fn test_find_by_with_related() {
+    let db = setup().await;
+    let ada = insert(&db, 1, "Ada", None).await;
+    insert(&db, 2, "Grace", None).await;
+    let mut posts = Vec::new();
+    for (id, title) in [(1, "Notes"), (2, "Engines")] {
+        let post = post::ActiveModel { id: Set(id), user_id: Set(1), title: Set(title.to_string()) };
+        posts.push(post.insert(&db).await.unwrap());
+    }
+
+    let filter = user::ModelOptional { id: 0, name: Some("Ada".to_string()), email: None };
+    let mut found = filter.find_by_with_related(&db).await.unwrap();
+    // Related rows come back in no particular order.
+    found.iter_mut().for_each(|(_, related)| related.sort_by_key(|post| post.id));
+    assert_eq!(found, vec![(ada, posts)]);
+}
+
+#[tokio::test]
+async fn test_find_by_with_exclusive_lock() {
+    let db = setup().await;
+    let optional = note::ModelOptional { id: 1, title: Some("draft".to_string()), saved_as: String::new() };
+    let inserted = optional.clone().to_active(&db).await.unwrap().insert(&db).await.unwrap();
+
+    // SQLite has no row locks, so the locked query behaves like the shared one here.
+    let txn = db.begin().await.unwrap();
+    assert_eq!(optional.clone().find_by(&txn).await.unwrap(), vec![inserted.clone()]);
+    txn.commit().await.unwrap();
+    assert_eq!(optional.find_by_shared(&db).await.unwrap(), vec![inserted]);
+}
+
+#[tokio::test]
+async fn test_version_column_checks_and_bumps() {
+    let db = setup().await;
+    // `to_active()` bumps the version on every write, inserts included.
+    let draft = document::ModelOptional { id: 1, body: Some("draft".to_string()), version: 0 };
+    let inserted = draft.to_active().insert(&db).await.unwrap();
+    assert_eq!(inserted.version, 1);
+
+    let current = document::ModelOptional::find_and_check_version(&db, 1, 1).await.unwrap();
+    let patch = document::ModelOptional { id: 1, body: Some("final".to_string()), version: current.version };
+    let updated = patch.to_active().update(&db).await.unwrap();
+    assert_eq!(updated, document::Model { id: 1, body: "final".to_string(), version: 2 });
+
+    assert_eq!(
+        document::ModelOptional::find_and_check_version(&db, 1, 1).await,
+        Err(OptimisticLockError::VersionMismatch { expected: 1, found: 2 })
+    );
+    assert_eq!(document::ModelOptional::find_and_check_version(&db, 2, 1).await, Err(OptimisticLockError::NotFound));
+
+    // The bump wraps around rather than overflowing.
+    let wrapped = document::ModelOptional { id: 1, body: None, version: i32::MAX }.to_active();
+    assert_eq!(wrapped.version, Set(i32::MIN));
+}
+
+#[tokio::test]
+async fn test_update_with_version_check_rejects_stale_writes() {
+    let db = setup().await;
+    let draft = document::ModelOptional { id: 1, body: Some("draft".to_string()), version: 0 };
+    draft.to_active().insert(&db).await.unwrap();
+
+    // Both writers read version 1; only the first update matches it.
+    let first = document::ModelOptional { id: 1, body: Some("first".to_string()), version: 1 };
+    let second = document::ModelOptional { id: 1, body: Some("second".to_string()), version: 1 };
+    let updated = first.update_with_version_check(&db).await.unwrap();
+    assert_eq!(updated, document::Model { id: 1, body: "first".to_string(), version: 2 });
+    assert_eq!(
+        second.update_with_version_check(&db).await,
+        Err(OptimisticLockError::VersionMismatch { expected: 1, found: 2 })
+    );
+    assert_eq!(document::Entity::find_by_id(1).one(&db).await.unwrap(), Some(updated));
+
+    let missing = document::ModelOptional { id: 2, body: Some("missing".to_string()), version: 1 };
+    assert_eq!(missing.update_with_version_check(&db).await, Err(OptimisticLockError::NotFound));
+}
+
+#[tokio::test]
+async fn test_insert_and_update_returning() {
+    let db = setup().await;
+    let draft = note::ModelOptional { id: 1, title: Some("  draft ".to_string()), saved_as: String::new() };
+    let inserted = draft.insert_and_return(&db).await.unwrap();
+    assert_eq!(inserted, note::Model { id: 1, title: "draft".to_string(), saved_as: "insert".to_string() });
+
+    let patch = note::ModelOptional { id: 0, title: Some("final".to_string()), saved_as: String::new() };
+    let updated = patch.update_and_return(1, &db).await.unwrap();
+    assert_eq!(updated, note::Model { id: 1, title: "final".to_string(), saved_as: "update".to_string() });
+    assert_eq!(note::Entity::find_by_id(1).one(&db).await.unwrap(), Some(updated));
+}
+
+#[tokio::test]
+async fn test_non_zero_fields_are_stored_as_integers() {
+    use std::num::{NonZeroI64, NonZeroU32};
+
+    let db = setup().await;
+    let optional = line_item_patch::LineItemOptional { id: 1, quantity: NonZeroU32::new(3), offset: NonZeroI64::new(-2) };
+    let inserted = optional.clone().to_active().insert(&db).await.unwrap();
+    assert_eq!(inserted, line_item::Model { id: 1, quantity: 3, offset: Some(-2) });
+    assert_eq!(optional.find_by(&db).await.unwrap(), vec![inserted]);
+}
+
+#[tokio::test]
+async fn test_before_save_is_told_insert_or_update() {
+    let db = setup().await;
+    let note = |id, title: &str| note::ModelOptional { id, title: Some(title.to_string()), saved_as: String::new() };
+    let saved_as = |model: note::Model| model.saved_as;
+
+    assert_eq!(saved_as(note(1, "a").insert_and_return(&db).await.unwrap()), "insert");
+    assert_eq!(saved_as(note(0, "b").update_and_return(1, &db).await.unwrap()), "update");
+    let (created, _) = note::ModelOptional::find_or_create(&db, 2, note(0, "c")).await.unwrap();
+    assert_eq!(saved_as(created), "insert");
+    assert_eq!(saved_as(note(2, "d").save(&db).await.unwrap()), "update");
+
+    // `update_many` bypasses `ActiveModelBehavior`, so only the generated hook call runs.
+    db.execute_unprepared("UPDATE notes SET saved_as = 'reset'").await.unwrap();
+    assert_eq!(note::ModelOptional::batch_update(&db, vec![1, 2], note(0, "f")).await.unwrap(), 2);
+    let rows = note::Entity::find().all(&db).await.unwrap();
+    assert!(rows.iter().all(|row| row.saved_as == "update" && row.title == "f"));
+
+    let created = note(0, " g ").to_create_active(&db).await.unwrap().insert(&db).await.unwrap();
+    assert_eq!((created.id, created.title, created.saved_as), (3, "g".to_string(), "insert".to_string()));
+}
+
+#[tokio::test]
+async fn test_create_leaves_primary_key_to_database() {
+    let db = setup().await;
+    insert(&db, 1, "Ada", None).await;
+    let draft = |title: &str| post_draft::PostOptional { id: 0, user_id: Some(1), title: Some(title.to_string()) };
+    let first = draft("first").to_create_active().insert(&db).await.unwrap();
+    let second = draft("second").to_create_active().insert(&db).await.unwrap();
+    assert_eq!((first.id, second.id), (1, 2));
+    assert_eq!(post::Entity::find_by_id(2).one(&db).await.unwrap().map(|post| post.title), Some("second".to_string()));
+}
+
+#[tokio::test]
+async fn test_soft_delete_and_undelete() {
+    let db = setup().await;
+    let draft = article::ModelOptional { id: 1, title: Some("Draft".to_string()), deleted_at: None };
+    let stored = draft.to_active().insert(&db).await.unwrap();
+
+    // Only the timestamp is written; the rest of the patch is left out of the update.
+    let patch = article::ModelOptional { id: 1, title: Some("Ignored".to_string()), deleted_at: None };
+    let mut model = stored.clone().into_active_model();
+    patch.soft_delete(&mut model);
+    let deleted = model.update(&db).await.unwrap();
+    assert_eq!(deleted.title, "Draft");
+    assert!(deleted.deleted_at.is_some());
+
+    let mut model = deleted.into_active_model();
+    article::ModelOptional::soft_undelete(&mut model);
+    model.update(&db).await.unwrap();
+    assert_eq!(article::Entity::find_by_id(1).one(&db).await.unwrap(), Some(stored));
+}
+
+#[tokio::test]
+async fn test_timestamp_is_set_on_every_write() {
+    let db = setup().await;
+    let draft = article::ModelOptional { id: 1, title: Some("Draft".to_string()), deleted_at: None };
+    let stored = draft.to_active().insert(&db).await.unwrap();
+
+    db.execute_unprepared("UPDATE articles SET updated_at = '2000-01-01 00:00:00'").await.unwrap();
+    let patch = article::ModelOptional { id: 1, title: Some("Final".to_string()), deleted_at: None };
+    let updated = patch.to_active().update(&db).await.unwrap();
+    assert_eq!(updated.title, "Final");
+    // Only a fresh timestamp can be later than the insert's after the reset to 2000.
+    assert!(updated.updated_at >= stored.updated_at);
+}
+
+#[tokio::test]
+async fn test_find_or_create_reports_failed_inserts() {
+    let db = setup().await;
+    // `name` is `NOT NULL`, and `to_active()` leaves it `NotSet` when the defaults lack it.
+    let defaults = user::ModelOptional { id: 0, name: None, email: None };
+    assert!(user::ModelOptional::find_or_create(&db, 1, defaults).await.is_err());
+    assert_eq!(user::Entity::find_by_id(1).one(&db).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_updates_of_missing_rows_fail() {
+    let db = setup().await;
+    let patch = || user::ModelOptional { id: 1, name: Some("Ada".to_string()), email: None };
+    assert_eq!(patch().to_active().update(&db).await, Err(DbErr::RecordNotUpdated));
+    assert_eq!(patch().save(&db).await, Err(DbErr::RecordNotUpdated));
+    assert_eq!(user::ModelOptional::batch_update(&db, vec![1, 2], patch()).await.unwrap(), 0);
+
+    let note = note::ModelOptional { id: 0, title: Some("final".to_string()), saved_as: String::new() };
+    assert_eq!(note.update_and_return(1, &db).await, Err(DbErr::RecordNotUpdated));
+    assert_eq!(user::Entity::find().all(&db).await.unwrap(), vec![]);
+}
+
+#[tokio::test]
+async fn test_queries_without_matches_are_empty() {
+    let db = setup().await;
+    insert(&db, 1, "Ada", None).await;
+    let filter = || user::ModelOptional { id: 0, name: Some("Grace".to_string()), email: None };
+    assert_eq!(filter().find_by(&db).await.unwrap(), vec![]);
+    assert_eq!(user::ModelOptional::paginate(&db, filter(), 0, 10).await.unwrap(), vec![]);
+    let everyone = user::ModelOptional { id: 0, name: None, email: None };
+    assert_eq!(user::ModelOptional::paginate(&db, everyone, 5, 10).await.unwrap(), vec![]);
+}
@@ -0,0 +1,594 @@
+use crate::case::RenameRule;
+use syn::{Attribute, Expr, Ident, LitStr, Path, Result};
+
+/// Generated methods and impls that `compact` leaves out unless they are named explicitly,
+/// e.g. `#[optionalize(compact, merge)]`.
+const COMPACT_OPT_INS: &[&str] = &["to_active", "patch", "from", "try_complete", "merge", "retain_fields", "sub", "transpose"];
+
+/// Generated items that refer to `sea_orm` and are dropped by `sea_orm_skip`.
+const SEA_ORM_ITEMS: &[&str] = &["to_active"];
+
+/// Options parsed from `#[optionalize(...)]` on the derived struct.
+#[derive(Clone, Default)]
+pub(crate) struct ContainerOptions {
+    /// `#[optionalize(builder)]`: also generate a `<Name>OptionalBuilder`.
+    pub builder: bool,
+    /// `#[optionalize(json_schema)]`: derive `schemars::JsonSchema` on the Optional struct.
+    pub json_schema: bool,
+    /// `#[optionalize(typescript)]`: derive `ts_rs::TS` on the Optional struct.
+    pub typescript: bool,
+    /// `#[optionalize(rename_all = "...")]`: case convention for every field's serialized name.
+    pub rename_all: Option<RenameRule>,
+    /// `#[optionalize(compact)]`: only emit the struct and the `OptionalizeTrait` impl.
+    pub compact: bool,
+    /// Items from `COMPACT_OPT_INS` re-enabled on top of `compact`.
+    pub opt_ins: Vec<String>,
+    /// `#[optionalize(sea_orm_skip)]`: emit no SeaORM integration at all.
+    pub sea_orm_skip: bool,
+    /// `#[optionalize(impl_from_hashmap)]`: implement `From<HashMap<String, String>>`.
+    pub impl_from_hashmap: bool,
+    /// `#[optionalize(from_env)]`: generate `from_env()`, reading `<prefix><FIELD>` variables.
+    pub from_env: bool,
+    /// `#[optionalize(prefix = "...")]`: variable name prefix for `from_env`.
+    pub env_prefix: Option<LitStr>,
+    /// `#[optionalize(to_hashmap)]`: generate `to_hashmap()`, which requires `ToString` fields.
+    pub to_hashmap: bool,
+    /// `#[optionalize(pub_fields)]`: make every Optional field `pub`.
+    pub pub_fields: bool,
+    /// `#[optionalize(accumulate_errors)]`: `try_complete()` reports every missing field at once.
+    pub accumulate_errors: bool,
+    /// `#[optionalize(sea_orm_into_active_model)]`: implement `sea_orm::IntoActiveModel<ActiveModel>`.
+    pub sea_orm_into_active_model: bool,
+    /// `#[optionalize(diesel_changeset)]`: derive `diesel::AsChangeset` on the Optional struct.
+    pub diesel_changeset: bool,
+    /// `#[optionalize(table = "...")]`: Diesel table the changeset targets.
+    pub table: Option<Path>,
+    /// `#[optionalize(module = "...")]` or its alias `emit_as_mod`: emit the generated
+    /// items inside `pub mod <module>`.
+    pub module: Option<Ident>,
+    /// `#[optionalize(wasm_bindgen)]`: export the Optional struct and field accessors to JavaScript.
+    pub wasm_bindgen: bool,
+    /// `#[optionalize(derive_copy)]`: also derive `Copy`; the user asserts every field type is `Copy`.
+    pub derive_copy: bool,
+    /// `#[optionalize(non_exhaustive)]`, or `#[non_exhaustive]` on the source struct.
+    pub non_exhaustive: bool,
+    /// `#[optionalize(allow_dead_code)]`: put `#[allow(dead_code)]` on the generated inherent impls.
+    pub allow_dead_code: bool,
+    /// `#[optionalize(serde_bound = "...")]`: forwarded as `#[serde(bound = "...")]`.
+    pub serde_bound: Option<LitStr>,
+    /// `#[optionalize(compact_debug)]`: a hand-written `Debug` impl that omits `None` fields.
+    pub compact_debug: bool,
+    /// `#[optionalize(serde_tag = "...")]`: forwarded as `#[serde(tag = "...")]`.
+    pub serde_tag: Option<LitStr>,
+    /// `#[optionalize(serde_content = "...")]`: forwarded as `#[serde(content = "...")]`.
+    pub serde_content: Option<LitStr>,
+    /// `#[optionalize(serde_untagged)]`: forwarded as `#[serde(untagged)]`.
+    pub serde_untagged: bool,
+    /// `#[optionalize(no_std)]`: reject items that need `std` or `alloc`.
+    pub no_std: bool,
+    /// `#[optionalize(sea_orm_prelude)]`: generated SeaORM code goes through `sea_orm::prelude::*`.
+    pub sea_orm_prelude: bool,
+    /// `#[optionalize(typestate)]`: also generate a `<Name>OptionalTypedBuilder` for `required` fields.
+    pub typestate: bool,
+    /// `#[optionalize(impl_update_from)]`: generate `update_from(&mut self, patch)` on the original struct.
+    pub impl_update_from: bool,
+    /// `#[optionalize(partial_eq_ignoring = "a, b")]`: implement `PartialEq` skipping these fields.
+    pub partial_eq_ignoring: Option<Vec<Ident>>,
+    /// `#[optionalize(sea_orm_timestamp = "...")]`: field that `to_active()` sets to the current time.
+    pub sea_orm_timestamp: Option<Ident>,
+    /// `#[optionalize(clone_on_none)]`: generate `patch_ref(&self, &original)`, which clones instead of moving.
+    pub clone_on_none: bool,
+    /// `#[optionalize(constrained_patch)]`: generate `constrained_patch(base, validate)`.
+    pub constrained_patch: bool,
+    /// `#[optionalize(sea_orm_model = "...")]`: SeaORM read model accepted by `from_model()`.
+    pub sea_orm_model: Option<Path>,
+    /// `#[optionalize(shrink)]`: generate `shrink(&original)`, which unsets unchanged fields.
+    pub shrink: bool,
+    /// `#[optionalize(graphql)]`: derive `async_graphql::InputObject` on the Optional struct.
+    pub graphql: bool,
+    /// `#[optionalize(graphql_input_name = "...")]`: GraphQL name of the input object.
+    pub graphql_input_name: Option<LitStr>,
+    /// `#[optionalize(sea_orm_find_by)]`: generate an async `find_by(db)` filtering on set fields.
+    pub sea_orm_find_by: bool,
+    /// `#[optionalize(sea_orm_active_model_behavior)]`: `to_active(db)` runs `ActiveModelBehavior::before_save`.
+    pub sea_orm_active_model_behavior: bool,
+    /// `#[optionalize(forward_attrs(a, b))]`: field attributes copied onto the Optional's fields.
+    pub forward_attrs: Vec<Ident>,
+    /// `#[optionalize(required_struct = "...")]`: name of the struct `into_required_parts()` splits off.
+    pub required_struct: Option<Ident>,
+    /// `#[optionalize(exclude = "a, b")]`: fields left out of the Optional altogether.
+    pub exclude: Vec<Ident>,
+    /// `#[optionalize(include = "a, b")]`: the only fields the Optional keeps.
+    pub include: Vec<Ident>,
+    /// `#[optionalize(impl_debug_redact = "a, b")]`: fields the `Debug` impl prints as `<redacted>`.
+    pub debug_redact: Vec<Ident>,
+    /// `#[optionalize(impl_hash)]`: derive `PartialEq`, `Eq` and `Hash` on the Optional struct.
+    pub impl_hash: bool,
+    /// `#[optionalize(sea_orm_default_values)]`: generate `to_create_active()`, defaulting unset fields.
+    pub sea_orm_default_values: bool,
+    /// `#[optionalize(newtype_wrap = "...")]`: name of a newtype around the Optional struct.
+    pub newtype_wrap: Option<Ident>,
+    /// `#[optionalize(sea_orm_soft_delete = "...")]`: `Option` timestamp field that `soft_delete()` sets.
+    pub sea_orm_soft_delete: Option<Ident>,
+    /// `#[optionalize(sea_orm_soft_undelete = "...")]`: `Option` timestamp field that `soft_undelete()` clears.
+    pub sea_orm_soft_undelete: Option<Ident>,
+    /// `#[optionalize(generic_optional)]`: generate `into_generic()` returning a `GenericOptional`.
+    pub generic_optional: bool,
+    /// `#[optionalize(deny_extra_fields)]`: require the Optional's fields to match `ActiveModel`'s exactly.
+    pub deny_extra_fields: bool,
+    /// `#[optionalize(sea_orm_pagination)]`: generate an async `paginate(db, filter, page, per_page)`.
+    pub sea_orm_pagination: bool,
+    /// `#[optionalize(doc = "...")]`, repeatable: doc comment lines for the Optional struct.
+    pub doc: Vec<LitStr>,
+    /// `#[optionalize(custom_deserializer)]`: hand-roll `Deserialize` instead of deriving it.
+    pub custom_deserializer: bool,
+    /// `#[optionalize(generate_constants)]`: emit a `FIELD_NAME_<FIELD>` constant per field.
+    pub generate_constants: bool,
+    /// `#[optionalize(sea_orm_with_transaction)]`: generate an async `save(db)` running in a transaction.
+    pub sea_orm_with_transaction: bool,
+    /// `#[optionalize(sea_orm_find_or_create)]`: generate an async `find_or_create(db, id, defaults)`.
+    pub sea_orm_find_or_create: bool,
+    /// `#[optionalize(sea_orm_exclusive_lock)]`: `find_by()` locks the rows; `find_by_shared()` does not.
+    pub sea_orm_exclusive_lock: bool,
+    /// `#[optionalize(sea_orm_preload(...))]`: related entity loaded by `find_by_with_related()`.
+    pub sea_orm_preload: Option<Path>,
+    /// `#[optionalize(sea_orm_active_model_type = "...")]`: path of the `ActiveModel` to build.
+    pub sea_orm_active_model_type: Option<Path>,
+    /// `#[optionalize(impl_ord)]`: derive `PartialEq`, `Eq`, `PartialOrd` and `Ord` on the Optional struct.
+    pub impl_ord: bool,
+    /// `#[optionalize(impl_snapshot)]`: generate `snapshot(&original)`, cloning every field.
+    pub impl_snapshot: bool,
+    /// `#[optionalize(sea_orm_returning)]`: generate `insert_and_return()` and `update_and_return()`.
+    pub sea_orm_returning: bool,
+    /// `#[optionalize(sea_orm_batch_update)]`: generate `batch_update(db, ids, patch)`.
+    pub sea_orm_batch_update: bool,
+    /// `#[optionalize(sea_orm_version_column = "...")]`: version field that `to_active()` increments.
+    pub sea_orm_version_column: Option<Ident>,
+}
+
+/// Parses a `"a, b"` string of field names.
+fn parse_field_list(lit: &LitStr) -> Result<Vec<Ident>> {
+    lit.value()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| syn::parse_str::<Ident>(name).map_err(|_| syn::Error::new_spanned(lit, "expected a comma-separated list of field names")))
+        .collect()
+}
+
+impl ContainerOptions {
+    /// Whether the generated item called `name` (one of `COMPACT_OPT_INS`) should be emitted.
+    pub fn generates(&self, name: &str) -> bool {
+        if self.sea_orm_skip && SEA_ORM_ITEMS.contains(&name) {
+            return false;
+        }
+        !self.compact || self.opt_ins.iter().any(|opt_in| opt_in == name)
+    }
+
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = ContainerOptions {
+            non_exhaustive: attrs.iter().any(|attr| attr.path().is_ident("non_exhaustive")),
+            ..ContainerOptions::default()
+        };
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("optionalize")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("builder") {
+                    options.builder = true;
+                    Ok(())
+                } else if meta.path.is_ident("typestate") {
+                    options.typestate = true;
+                    Ok(())
+                } else if meta.path.is_ident("json_schema") {
+                    if !cfg!(feature = "json-schema") {
+                        return Err(meta.error("`json_schema` requires the `json-schema` feature"));
+                    }
+                    options.json_schema = true;
+                    Ok(())
+                } else if meta.path.is_ident("typescript") {
+                    if !cfg!(feature = "ts") {
+                        return Err(meta.error("`typescript` requires the `ts` feature"));
+                    }
+                    options.typescript = true;
+                    Ok(())
+                } else if meta.path.is_ident("graphql") {
+                    if !cfg!(feature = "graphql") {
+                        return Err(meta.error("`graphql` requires the `graphql` feature"));
+                    }
+                    options.graphql = true;
+                    Ok(())
+                } else if meta.path.is_ident("doc") {
+                    options.doc.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("newtype_wrap") {
+                    options.newtype_wrap = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("required_struct") {
+                    options.required_struct = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("graphql_input_name") {
+                    options.graphql_input_name = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    options.rename_all = Some(RenameRule::from_lit(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_skip") {
+                    options.sea_orm_skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_prelude") {
+                    options.sea_orm_prelude = true;
+                    Ok(())
+                } else if meta.path.is_ident("shrink") {
+                    options.shrink = true;
+                    Ok(())
+                } else if meta.path.is_ident("clone_on_none") {
+                    options.clone_on_none = true;
+                    Ok(())
+                } else if meta.path.is_ident("constrained_patch") {
+                    options.constrained_patch = true;
+                    Ok(())
+                } else if meta.path.is_ident("impl_update_from") {
+                    options.impl_update_from = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_active_model_behavior") {
+                    options.sea_orm_active_model_behavior = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_default_values") {
+                    options.sea_orm_default_values = true;
+                    Ok(())
+                } else if meta.path.is_ident("deny_extra_fields") {
+                    options.deny_extra_fields = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_pagination") {
+                    options.sea_orm_pagination = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_with_transaction") {
+                    options.sea_orm_with_transaction = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_find_or_create") {
+                    options.sea_orm_find_or_create = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_exclusive_lock") {
+                    options.sea_orm_exclusive_lock = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_find_by") {
+                    options.sea_orm_find_by = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_soft_undelete") {
+                    options.sea_orm_soft_undelete = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_soft_delete") {
+                    options.sea_orm_soft_delete = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_preload") {
+                    meta.parse_nested_meta(|inner| {
+                        if options.sea_orm_preload.is_some() {
+                            return Err(inner.error("`sea_orm_preload` takes a single related entity"));
+                        }
+                        options.sea_orm_preload = Some(inner.path.clone());
+                        Ok(())
+                    })
+                } else if meta.path.is_ident("sea_orm_active_model_type") {
+                    options.sea_orm_active_model_type = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_model") {
+                    options.sea_orm_model = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_returning") {
+                    options.sea_orm_returning = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_batch_update") {
+                    options.sea_orm_batch_update = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_version_column") {
+                    options.sea_orm_version_column = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_timestamp") {
+                    options.sea_orm_timestamp = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("impl_from_hashmap") {
+                    options.impl_from_hashmap = true;
+                    Ok(())
+                } else if meta.path.is_ident("from_env") {
+                    options.from_env = true;
+                    Ok(())
+                } else if meta.path.is_ident("prefix") {
+                    options.env_prefix = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("to_hashmap") {
+                    options.to_hashmap = true;
+                    Ok(())
+                } else if meta.path.is_ident("pub_fields") {
+                    options.pub_fields = true;
+                    Ok(())
+                } else if meta.path.is_ident("accumulate_errors") {
+                    options.accumulate_errors = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_into_active_model") {
+                    options.sea_orm_into_active_model = true;
+                    Ok(())
+                } else if meta.path.is_ident("diesel_changeset") {
+                    if !cfg!(feature = "diesel") {
+                        return Err(meta.error("`diesel_changeset` requires the `diesel` feature"));
+                    }
+                    options.diesel_changeset = true;
+                    Ok(())
+                } else if meta.path.is_ident("table") {
+                    options.table = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("module") || meta.path.is_ident("emit_as_mod") {
+                    if options.module.is_some() {
+                        return Err(meta.error("`module` and `emit_as_mod` may only be given once"));
+                    }
+                    options.module = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("wasm_bindgen") {
+                    if !cfg!(feature = "wasm") {
+                        return Err(meta.error("`wasm_bindgen` requires the `wasm` feature"));
+                    }
+                    options.wasm_bindgen = true;
+                    Ok(())
+                } else if meta.path.is_ident("derive_copy") {
+                    options.derive_copy = true;
+                    Ok(())
+                } else if meta.path.is_ident("non_exhaustive") {
+                    options.non_exhaustive = true;
+                    Ok(())
+                } else if meta.path.is_ident("allow_dead_code") {
+                    options.allow_dead_code = true;
+                    Ok(())
+                } else if meta.path.is_ident("serde_bound") {
+                    options.serde_bound = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("compact_debug") {
+                    options.compact_debug = true;
+                    Ok(())
+                } else if meta.path.is_ident("serde_tag") {
+                    options.serde_tag = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("serde_content") {
+                    options.serde_content = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("serde_untagged") {
+                    options.serde_untagged = true;
+                    Ok(())
+                } else if meta.path.is_ident("no_std") {
+                    options.no_std = true;
+                    Ok(())
+                } else if meta.path.is_ident("partial_eq_ignoring") {
+                    options.partial_eq_ignoring = Some(parse_field_list(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("custom_deserializer") {
+                    options.custom_deserializer = true;
+                    Ok(())
+                } else if meta.path.is_ident("generate_constants") {
+                    options.generate_constants = true;
+                    Ok(())
+                } else if meta.path.is_ident("generic_optional") {
+                    if !cfg!(feature = "json") {
+                        return Err(meta.error("`generic_optional` requires the `json` feature"));
+                    }
+                    options.generic_optional = true;
+                    Ok(())
+                } else if meta.path.is_ident("impl_snapshot") {
+                    options.impl_snapshot = true;
+                    Ok(())
+                } else if meta.path.is_ident("impl_ord") {
+                    options.impl_ord = true;
+                    Ok(())
+                } else if meta.path.is_ident("impl_hash") {
+                    options.impl_hash = true;
+                    Ok(())
+                } else if meta.path.is_ident("exclude") {
+                    options.exclude = parse_field_list(&meta.value()?.parse()?)?;
+                    Ok(())
+                } else if meta.path.is_ident("include") {
+                    options.include = parse_field_list(&meta.value()?.parse()?)?;
+                    Ok(())
+                } else if meta.path.is_ident("impl_debug_redact") {
+                    options.debug_redact = parse_field_list(&meta.value()?.parse()?)?;
+                    Ok(())
+                } else if meta.path.is_ident("forward_attrs") {
+                    meta.parse_nested_meta(|inner| {
+                        let name = inner
+                            .path
+                            .get_ident()
+                            .ok_or_else(|| inner.error("expected an attribute name"))?;
+                        options.forward_attrs.push(name.clone());
+                        Ok(())
+                    })
+                } else if meta.path.is_ident("compact") {
+                    options.compact = true;
+                    Ok(())
+                } else if let Some(opt_in) = COMPACT_OPT_INS.iter().find(|opt_in| meta.path.is_ident(opt_in)) {
+                    options.opt_ins.push(opt_in.to_string());
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported optionalize attribute"))
+                }
+            })?;
+            if options.serde_content.is_some() && options.serde_tag.is_none() {
+                return Err(syn::Error::new_spanned(attr, "`serde_content` requires `serde_tag`"));
+            }
+            if options.serde_untagged && options.serde_tag.is_some() {
+                return Err(syn::Error::new_spanned(attr, "`serde_untagged` cannot be combined with `serde_tag`"));
+            }
+            if options.custom_deserializer {
+                let derive_only = [
+                    ("serde_bound", options.serde_bound.is_some()),
+                    ("serde_tag", options.serde_tag.is_some()),
+                    ("serde_untagged", options.serde_untagged),
+                ];
+                if let Some((name, _)) = derive_only.iter().find(|(_, enabled)| *enabled) {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!("`{}` configures the derived `Deserialize` and cannot be combined with `custom_deserializer`", name),
+                    ));
+                }
+            }
+            if !options.exclude.is_empty() && !options.include.is_empty() {
+                return Err(syn::Error::new_spanned(attr, "`include` cannot be combined with `exclude`"));
+            }
+            if (!options.exclude.is_empty() || !options.include.is_empty()) && options.builder {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`exclude` and `include` cannot be combined with `builder`: `build_checked()` has no value for the left-out fields",
+                ));
+            }
+            if options.sea_orm_timestamp.is_some() && options.builder {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`sea_orm_timestamp` cannot be combined with `builder`: `build_checked()` has no value for the timestamp field",
+                ));
+            }
+            if options.constrained_patch && !options.generates("patch") {
+                return Err(syn::Error::new_spanned(attr, "`constrained_patch` requires `patch`, which `compact` leaves out"));
+            }
+            if options.env_prefix.is_some() && !options.from_env {
+                return Err(syn::Error::new_spanned(attr, "`prefix` requires `from_env`"));
+            }
+            if options.no_std {
+                let std_only = [
+                    ("impl_from_hashmap", options.impl_from_hashmap),
+                    ("to_hashmap", options.to_hashmap),
+                    ("accumulate_errors", options.accumulate_errors),
+                    ("wasm_bindgen", options.wasm_bindgen),
+                    ("generic_optional", options.generic_optional),
+                    ("from_env", options.from_env),
+                    ("custom_deserializer", options.custom_deserializer),
+                    ("sea_orm_find_by", options.sea_orm_find_by),
+                    ("sea_orm_pagination", options.sea_orm_pagination),
+                    ("sea_orm_batch_update", options.sea_orm_batch_update),
+                ];
+                if let Some((name, _)) = std_only.iter().find(|(_, enabled)| *enabled) {
+                    return Err(syn::Error::new_spanned(attr, format!("`{}` cannot be combined with `no_std`", name)));
+                }
+            }
+            if options.impl_hash && options.partial_eq_ignoring.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`impl_hash` hashes every field and cannot be combined with `partial_eq_ignoring`",
+                ));
+            }
+            if options.impl_ord && options.partial_eq_ignoring.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`impl_ord` compares every field and cannot be combined with `partial_eq_ignoring`",
+                ));
+            }
+            if options.sea_orm_exclusive_lock && !options.sea_orm_find_by {
+                return Err(syn::Error::new_spanned(attr, "`sea_orm_exclusive_lock` requires `sea_orm_find_by`"));
+            }
+            if options.sea_orm_preload.is_some() && !options.sea_orm_find_by {
+                return Err(syn::Error::new_spanned(attr, "`sea_orm_preload` requires `sea_orm_find_by`"));
+            }
+            if options.graphql_input_name.is_some() && !options.graphql {
+                return Err(syn::Error::new_spanned(attr, "`graphql_input_name` requires `graphql`"));
+            }
+            if options.table.is_some() && !options.diesel_changeset {
+                return Err(syn::Error::new_spanned(attr, "`table` requires `diesel_changeset`"));
+            }
+            if options.sea_orm_skip && options.sea_orm_into_active_model {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`sea_orm_into_active_model` cannot be combined with `sea_orm_skip`",
+                ));
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Options parsed from `#[optionalize(...)]` on a single field.
+#[derive(Clone, Default)]
+pub(crate) struct FieldOptions {
+    /// `#[optionalize(into)]`: builder setters accept `impl Into<T>`.
+    pub into: bool,
+    /// `#[optionalize(required)]`: the typestate builder's `build()` needs this field set, and
+    /// `into_required_parts()` moves it into the required struct.
+    pub required: bool,
+    /// `#[optionalize(rename = "...")]`: serialized name of the field.
+    pub rename: Option<LitStr>,
+    /// `#[optionalize(rename_all = "...")]`: overrides the struct-level `rename_all` for this field.
+    pub rename_all: Option<RenameRule>,
+    /// `#[optionalize(flatten_option)]`: an `Option<Option<T>>` field becomes `Option<T>`.
+    pub flatten_option: bool,
+    /// `#[optionalize(skip)]`: the field keeps its type and is left out of patching; replaces `#[optionalize_ignore]`.
+    pub skip: bool,
+    /// `#[optionalize(sea_orm_primary_key)]`: alias for `#[optionalize(skip)]`.
+    pub primary_key: bool,
+    /// `#[optionalize(sea_orm_column_type = "...")]`: `sea_orm::ColumnType` variant of the column.
+    pub column_type: Option<Expr>,
+    /// `#[optionalize(sea_orm_not_set_default)]`: `to_active()` emits `NotSet` for this ignored field.
+    pub not_set_default: bool,
+    /// `#[optionalize(schema_as = "...")]`: type the JSON schema describes in place of the field type.
+    pub schema_as: Option<syn::Type>,
+    /// `#[optionalize(sea_orm_active_value_set = "...")]`: function applied to the value inside `ActiveValue::Set`.
+    pub active_value_set: Option<Expr>,
+    /// `#[optionalize(sea_orm_column_name = "...")]`: `ActiveModel` field this field maps to.
+    pub column_name: Option<Ident>,
+    /// `#[optionalize(sea_orm_skip_in_active)]`: the field has no `ActiveModel` counterpart.
+    pub skip_in_active: bool,
+    /// `#[optionalize(sea_orm_insert_id)]`: `to_create_active()` sets this ignored field instead of keeping it `Unchanged`.
+    pub insert_id: bool,
+    /// Set for the field named by the container-level `sea_orm_timestamp`; never parsed from field attributes.
+    pub timestamp: bool,
+    /// Set for the field named by the container-level `sea_orm_version_column`; never parsed from field attributes.
+    pub version: bool,
+}
+
+impl FieldOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = FieldOptions::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("optionalize")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("into") {
+                    options.into = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("required") {
+                    options.required = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    options.rename_all = Some(RenameRule::from_lit(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("flatten_option") {
+                    options.flatten_option = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_primary_key") {
+                    options.primary_key = true;
+                    Ok(())
+                } else if meta.path.is_ident("schema_as") {
+                    options.schema_as = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_insert_id") {
+                    options.insert_id = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_skip_in_active") {
+                    options.skip_in_active = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_column_name") {
+                    options.column_name = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_active_value_set") {
+                    options.active_value_set = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_not_set_default") {
+                    options.not_set_default = true;
+                    Ok(())
+                } else if meta.path.is_ident("sea_orm_column_type") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    options.column_type = Some(lit.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported optionalize field attribute"))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
@@ -0,0 +1,654 @@
+//! Attribute combinations the derive rejects or warns about, each checked by a `compile_fail`
+//! doctest; the fixtures are valid apart from the combination shown.
+//!
+//! # Field options
+//!
+//! `into` shapes the builder's setters, so it requires `builder`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip)]
+//! pub struct User {
+//!     #[optionalize(into)]
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `required` only means something to the typestate builder or `required_struct`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip)]
+//! pub struct User {
+//!     #[optionalize(required)]
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! and ignored fields are always set, so they cannot be `required`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, typestate)]
+//! pub struct User {
+//!     #[optionalize(skip, required)]
+//!     pub id: i32,
+//! }
+//! ```
+//!
+//! `schema_as` requires `json_schema`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip)]
+//! pub struct User {
+//!     #[optionalize(schema_as = "String")]
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `sea_orm_not_set_default` only applies to ignored fields:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! pub struct User {
+//!     #[optionalize(sea_orm_not_set_default)]
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! and so does `sea_orm_insert_id`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! pub struct User {
+//!     #[optionalize(sea_orm_insert_id)]
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `flatten_option` requires an `Option<Option<T>>` field:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip)]
+//! pub struct User {
+//!     #[optionalize(flatten_option)]
+//!     pub nickname: Option<String>,
+//! }
+//! ```
+//!
+//! `deny_extra_fields` rejects a rename that no longer matches the column:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(deny_extra_fields)]
+//! pub struct User {
+//!     #[optionalize(rename = "fullName")]
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! # Container options
+//!
+//! `include` and `exclude` cannot be combined:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, include = "name", exclude = "email")]
+//! pub struct User {
+//!     pub name: String,
+//!     pub email: String,
+//! }
+//! ```
+//!
+//! `exclude` is unavailable with `builder`, whose `build_checked()` would have no value for the
+//! left-out field:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, exclude = "email", builder)]
+//! pub struct User {
+//!     pub name: String,
+//!     pub email: String,
+//! }
+//! ```
+//!
+//! `exclude` must name a field:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, exclude = "phone")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `constrained_patch` requires `patch`, which `compact` leaves out:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(compact, constrained_patch)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `prefix` requires `from_env`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, prefix = "APP_")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `module` and its alias `emit_as_mod` may only be given once:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, module = "a", emit_as_mod = "b")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `impl_hash` hashes every field, so it cannot skip any with `partial_eq_ignoring`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, impl_hash, partial_eq_ignoring = "name")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! and neither can `impl_ord`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, impl_ord, partial_eq_ignoring = "name")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `partial_eq_ignoring` must name a field:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, partial_eq_ignoring = "phone")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `impl_debug_redact` must name a field:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, impl_debug_redact = "phone")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `serde_content` requires `serde_tag`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, serde_content = "data")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `serde_untagged` cannot be combined with `serde_tag`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, serde_tag = "type", serde_untagged)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `custom_deserializer` replaces the derive that the `serde_*` options configure:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, custom_deserializer, serde_tag = "type")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! and does not support generic structs:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, custom_deserializer)]
+//! pub struct User<T> {
+//!     pub name: T,
+//! }
+//! ```
+//!
+//! and neither does `required_struct`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, typestate, required_struct = "UserRequired")]
+//! pub struct User<T> {
+//!     #[optionalize(required)]
+//!     pub name: T,
+//! }
+//! ```
+//!
+//! `graphql_input_name` requires `graphql`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, graphql_input_name = "UserInput")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `table` requires `diesel_changeset`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, table = "users")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `impl_update_from` adds a method to the original struct, which `optionalize!` cannot do for a
+//! foreign one:
+//!
+//! ```compile_fail
+//! use optionalize::optionalize;
+//! mod other_crate {
+//!     pub struct User {
+//!         pub name: String,
+//!     }
+//! }
+//!
+//! optionalize!(
+//!     #[optionalize(sea_orm_skip, impl_update_from)]
+//!     other_crate::User {
+//!         pub name: String,
+//!     }
+//! );
+//! ```
+//!
+//! `no_std` rejects the options whose generated code needs `std`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, no_std, to_hashmap)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! # SeaORM
+//!
+//! `sea_orm_into_active_model` cannot be combined with `sea_orm_skip`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_skip, sea_orm_into_active_model)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `sea_orm_exclusive_lock` requires `sea_orm_find_by`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_exclusive_lock)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! and so does `sea_orm_preload`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_preload(Entity))]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `sea_orm_find_or_create` needs exactly one primary key:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_find_or_create)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `sea_orm_returning` needs one too:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_returning)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! so does `sea_orm_batch_update`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_batch_update)]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! and `sea_orm_version_column`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! #     pub version: sea_orm::ActiveValue<i32>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_version_column = "version")]
+//! pub struct User {
+//!     pub name: String,
+//!     pub version: i32,
+//! }
+//! ```
+//!
+//! whose field must also fit in the `i64` that `OptimisticLockError` reports:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub id: sea_orm::ActiveValue<i32>,
+//! #     pub version: sea_orm::ActiveValue<u64>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_version_column = "version")]
+//! pub struct User {
+//!     #[optionalize(sea_orm_primary_key)]
+//!     pub id: i32,
+//!     pub version: u64,
+//! }
+//! ```
+//!
+//! `sea_orm_timestamp` must name a field:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub name: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_timestamp = "updated_at")]
+//! pub struct User {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! `no_std` also rejects the field option whose generated `column_types()` returns a `Vec`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub value: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(no_std)]
+//! pub struct Setting {
+//!     #[optionalize(sea_orm_column_type = "Text")]
+//!     pub value: String,
+//! }
+//! ```
+//!
+//! and so do the SeaORM query helpers, which return `Vec`s as well:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub value: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(no_std, sea_orm_find_by)]
+//! pub struct Setting {
+//!     pub value: String,
+//! }
+//! ```
+//!
+//! `sea_orm_timestamp` writes `Utc::now().naive_utc()`, so its field must be a
+//! `NaiveDateTime`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub title: sea_orm::ActiveValue<String>,
+//! #     pub updated_at: sea_orm::ActiveValue<i64>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_timestamp = "updated_at")]
+//! pub struct Note {
+//!     pub title: String,
+//!     pub updated_at: i64,
+//! }
+//! ```
+//!
+//! and `build_checked()` has no value for it:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # type NaiveDateTime = i64;
+//! # pub struct ActiveModel {
+//! #     pub title: sea_orm::ActiveValue<String>,
+//! #     pub updated_at: sea_orm::ActiveValue<NaiveDateTime>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_timestamp = "updated_at", builder)]
+//! pub struct Note {
+//!     pub title: String,
+//!     pub updated_at: NaiveDateTime,
+//! }
+//! ```
+//!
+//! `sea_orm_soft_delete` likewise needs an `Option<NaiveDateTime>`:
+//!
+//! ```compile_fail
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub title: sea_orm::ActiveValue<String>,
+//! #     pub deleted_at: sea_orm::ActiveValue<Option<bool>>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! #[optionalize(sea_orm_soft_delete = "deleted_at")]
+//! pub struct Post {
+//!     pub title: String,
+//!     pub deleted_at: Option<bool>,
+//! }
+//! ```
+//!
+//! # Warnings
+//!
+//! An ignored `Option` field warns, since `to_active()` leaves it `NotSet` when it is `None`:
+//!
+//! ```compile_fail
+//! #![deny(deprecated)]
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub title: sea_orm::ActiveValue<String>,
+//! #     pub archived_by: sea_orm::ActiveValue<Option<i32>>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! pub struct Post {
+//!     pub title: String,
+//!     #[optionalize(skip)]
+//!     pub archived_by: Option<i32>,
+//! }
+//! ```
+//!
+//! and so does an `Option` primary key, which is not kept `Unchanged` either:
+//!
+//! ```compile_fail
+//! #![deny(deprecated)]
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub id: sea_orm::ActiveValue<Option<i32>>,
+//! #     pub title: sea_orm::ActiveValue<String>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! pub struct Post {
+//!     #[optionalize(sea_orm_primary_key)]
+//!     pub id: Option<i32>,
+//!     pub title: String,
+//! }
+//! ```
+//!
+//! Saying what the field should do silences the warning:
+//!
+//! ```
+//! #![deny(deprecated)]
+//! use optionalize::Optionalize;
+//! # use optionalize::sea_orm;
+//! # pub struct ActiveModel {
+//! #     pub title: sea_orm::ActiveValue<String>,
+//! #     pub archived_by: sea_orm::ActiveValue<Option<i32>>,
+//! # }
+//!
+//! #[derive(Optionalize)]
+//! pub struct Post {
+//!     pub title: String,
+//!     #[optionalize(skip, sea_orm_not_set_default)]
+//!     pub archived_by: Option<i32>,
+//! }
+//! ```
@@ -1,3 +1,15 @@
+mod attr;
+mod case;
+#[cfg(doctest)]
+mod compile_fail;
+
+use attr::{ContainerOptions, FieldOptions};
+use case::RenameRule;
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, DeriveInput, Data, Type, Meta};
+
 /// The `Optionalize` macro generates a new struct with optional fields.
 ///
 /// For any struct that derives `Optionalize`, the macro will generate a new struct
@@ -10,8 +22,13 @@
 /// # Example
 ///
 /// ```rust
-/// use optionalize_macro::{Optionalize};
-/// use optionalize_core::OptionalizeTrait;
+/// use optionalize::{Optionalize, OptionalizeTrait};
+/// # use optionalize::sea_orm;
+/// # pub struct ActiveModel {
+/// #     pub id: sea_orm::ActiveValue<i32>,
+/// #     pub name: sea_orm::ActiveValue<String>,
+/// #     pub description: sea_orm::ActiveValue<Option<String>>,
+/// # }
 ///
 /// #[derive(Optionalize)]
 /// pub struct MyStruct {
@@ -21,22 +38,98 @@
 /// }
 ///
 /// // The generated struct will look like:
-/// let test = MyStruct::Optional {
+/// let test: <MyStruct as OptionalizeTrait>::Optional = MyStructOptional {
 ///     id: Some(1i32),
 ///     name: Some("Name".to_string()),
 ///     description: Some("Test Description".to_string())
 /// };
 /// ```
-
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Type, Meta};
-
-#[proc_macro_derive(Optionalize, attributes(optionalize_ignore))]
+#[proc_macro_derive(Optionalize, attributes(optionalize_ignore, optionalize))]
 pub fn derive_optionalize(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = parse_macro_input!(input as DeriveInput);
+    let struct_path = input.ident.clone().into();
+    expand(input, struct_path, false)
+}
+
+/// Generates an Optional struct for a struct defined elsewhere, e.g. in another crate.
+///
+/// The field definitions are repeated inside the invocation, using the same attributes
+/// the derive accepts:
+///
+/// ```rust,ignore
+/// optionalize!(
+///     #[optionalize(sea_orm_skip)]
+///     other_crate::User {
+///         pub id: i32,
+///         pub name: String,
+///     }
+/// );
+/// ```
+///
+/// The listed fields must match the original struct exactly; a missing or extra field
+/// fails to compile:
+///
+/// ```rust,compile_fail
+/// # use optionalize::optionalize;
+/// mod other_crate {
+///     pub struct User {
+///         pub id: i32,
+///         pub name: String,
+///     }
+/// }
+///
+/// optionalize!(
+///     #[optionalize(compact, sea_orm_skip)]
+///     other_crate::User {
+///         pub id: i32,
+///     }
+/// );
+/// ```
+///
+/// Because the original struct is foreign, the orphan rule forbids implementing
+/// `OptionalizeTrait` or adding inherent methods (such as `as_optional()`) to it; every
+/// other item is generated just like with `#[derive(Optionalize)]`.
+#[proc_macro]
+pub fn optionalize(input: TokenStream) -> TokenStream {
+    let ForeignStruct { attrs, path, fields } = parse_macro_input!(input as ForeignStruct);
+    let Some(ident) = path.segments.last().map(|segment| segment.ident.clone()) else {
+        return syn::Error::new_spanned(path, "expected a struct path").to_compile_error().into();
+    };
+    let input = DeriveInput {
+        attrs,
+        vis: syn::Visibility::Inherited,
+        ident,
+        generics: syn::Generics::default(),
+        data: Data::Struct(syn::DataStruct {
+            struct_token: Default::default(),
+            fields: syn::Fields::Named(fields),
+            semi_token: None,
+        }),
+    };
+    expand(input, path, true)
+}
+
+/// Input of `optionalize!`: outer attributes, the struct path and its named fields.
+struct ForeignStruct {
+    attrs: Vec<syn::Attribute>,
+    path: syn::Path,
+    fields: syn::FieldsNamed,
+}
 
+impl syn::parse::Parse for ForeignStruct {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(ForeignStruct {
+            attrs: input.call(syn::Attribute::parse_outer)?,
+            path: input.parse()?,
+            fields: input.parse()?,
+        })
+    }
+}
+
+/// Expands the Optional struct and its impls. `struct_path` names the original struct
+/// in generated code; `foreign` skips the items the orphan rule forbids on foreign types.
+fn expand(input: DeriveInput, struct_path: syn::Path, foreign: bool) -> TokenStream {
     // Get the struct name
     let struct_name = input.ident.clone();
 
@@ -52,80 +145,2647 @@ pub fn derive_optionalize(input: TokenStream) -> TokenStream {
             .to_compile_error()
             .into();
     };
-    let fields = fields.into_iter().map(|field| {
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let optional_ty = quote! { #optional_struct_name #ty_generics };
+    let struct_ty = quote! { #struct_path #ty_generics };
+
+    let container_options = match ContainerOptions::from_attrs(&input.attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    // Taken from the input rather than from the processed field list, so the parity check
+    // emitted below catches the expansion dropping or inventing a field.
+    let source_fields: Vec<_> = fields.iter().filter_map(|field| field.ident.clone()).collect();
+    let parity_fields = source_fields.iter().filter(|name| {
+        container_options.sea_orm_timestamp.as_ref() != Some(*name)
+            && !container_options.exclude.contains(name)
+            && (container_options.include.is_empty() || container_options.include.contains(name))
+    });
+    let mut field_options = Vec::new();
+    for field in &fields {
+        let options = match FieldOptions::from_attrs(&field.attrs) {
+            Ok(options) => options,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        if options.into && !container_options.builder && !container_options.typestate {
+            return syn::Error::new_spanned(field, "`#[optionalize(into)]` requires `#[optionalize(builder)]`")
+                .to_compile_error()
+                .into();
+        }
+        if options.required && !container_options.typestate && container_options.required_struct.is_none() {
+            return syn::Error::new_spanned(
+                field,
+                "`#[optionalize(required)]` requires `#[optionalize(typestate)]` or `#[optionalize(required_struct = \"...\")]`",
+            )
+                .to_compile_error()
+                .into();
+        }
+        let is_ignored =
+            options.primary_key || options.skip || field.attrs.iter().any(|attr| attr.path().is_ident("optionalize_ignore"));
+        if options.required && is_ignored {
+            return syn::Error::new_spanned(field, "ignored fields are always set and cannot be `#[optionalize(required)]`")
+                .to_compile_error()
+                .into();
+        }
+        // A PATCH body keyed by the renamed name would not line up with the column, unless
+        // `sea_orm_column_name` names that column.
+        if let (true, Some(rename), Some(name)) = (container_options.deny_extra_fields, &options.rename, active_model_field(field, &options)) {
+            if name.unraw() != rename.value() {
+                return syn::Error::new_spanned(
+                    rename,
+                    format!("`deny_extra_fields`: `{}` is renamed to `{}`, which is not an `ActiveModel` column", name, rename.value()),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+        if options.schema_as.is_some() && !container_options.json_schema {
+            return syn::Error::new_spanned(field, "`#[optionalize(schema_as)]` requires `#[optionalize(json_schema)]`")
+                .to_compile_error()
+                .into();
+        }
+        if options.not_set_default && !is_ignored {
+            return syn::Error::new_spanned(field, "`#[optionalize(sea_orm_not_set_default)]` only applies to ignored fields")
+                .to_compile_error()
+                .into();
+        }
+        if options.insert_id && !is_ignored {
+            return syn::Error::new_spanned(field, "`#[optionalize(sea_orm_insert_id)]` only applies to ignored fields")
+                .to_compile_error()
+                .into();
+        }
+        // `column_types()` returns a `Vec`.
+        if options.column_type.is_some() && container_options.no_std {
+            return syn::Error::new_spanned(field, "`#[optionalize(sea_orm_column_type)]` cannot be combined with `no_std`")
+                .to_compile_error()
+                .into();
+        }
+        if options.flatten_option && option_inner_type(&field.ty).and_then(option_inner_type).is_none() {
+            return syn::Error::new_spanned(&field.ty, "`#[optionalize(flatten_option)]` requires an `Option<Option<T>>` field")
+                .to_compile_error()
+                .into();
+        }
+        field_options.push(options);
+    }
+    // `sea_orm_timestamp` names a field that is left out of the Optional, except that
+    // `to_active()` always sets it to the current time.
+    if let Some(timestamp) = &container_options.sea_orm_timestamp {
+        let Some((index, field)) = fields.iter().enumerate().find(|(_, field)| field.ident.as_ref() == Some(timestamp)) else {
+            return syn::Error::new_spanned(timestamp, format!("`sea_orm_timestamp` names unknown field `{}`", timestamp))
+                .to_compile_error()
+                .into();
+        };
+        if !is_naive_date_time(&field.ty) {
+            return syn::Error::new_spanned(
+                &field.ty,
+                "`sea_orm_timestamp` must be a `NaiveDateTime` (SeaORM's `DateTime`), which it sets to `Utc::now().naive_utc()`",
+            )
+            .to_compile_error()
+            .into();
+        }
+        field_options[index].timestamp = true;
+    }
+    // Likewise `sea_orm_version_column`, which `to_active()` writes back incremented.
+    if let Some(version) = &container_options.sea_orm_version_column {
+        let Some((index, field)) = fields.iter().enumerate().find(|(_, field)| field.ident.as_ref() == Some(version)) else {
+            return syn::Error::new_spanned(version, format!("`sea_orm_version_column` names unknown field `{}`", version))
+                .to_compile_error()
+                .into();
+        };
+        // `OptimisticLockError` reports versions as `i64`, so the column must convert losslessly.
+        let lossless = ["i8", "i16", "i32", "i64", "u8", "u16", "u32"];
+        let is_lossless = match &field.ty {
+            Type::Path(type_path) => type_path.qself.is_none() && lossless.iter().any(|name| type_path.path.is_ident(name)),
+            _ => false,
+        };
+        if !is_lossless {
+            return syn::Error::new_spanned(
+                &field.ty,
+                "`sea_orm_version_column` must be `i8`, `i16`, `i32`, `i64`, `u8`, `u16` or `u32`, which `OptimisticLockError` reports as `i64`",
+            )
+            .to_compile_error()
+            .into();
+        }
+        field_options[index].version = true;
+    }
+
+    let fields: Vec<_> = fields.into_iter().zip(field_options).map(|(mut field, options)| {
+        // A flattened `Option<Option<T>>` is handled as if it were declared `Option<T>`;
+        // only code that moves values between the original struct and the Optional needs
+        // to re-wrap it.
+        if options.flatten_option {
+            if let Some(inner) = option_inner_type(&field.ty) {
+                field.ty = inner.clone();
+            }
+        }
+        // Only the outermost segment matters, so opaque payloads such as
+        // `Option<Box<dyn Trait>>` are kept as they are rather than double-wrapped.
         let mut is_optional = false;
         if let Type::Path(type_path) = &field.ty {
             is_optional = type_path.path.segments.last().map(|f| f.ident == "Option").unwrap_or(false);
         }
+        if options.primary_key || options.skip || options.version {
+            return (field, true, is_optional, options);
+        }
         for attr in &field.attrs {
             match &attr.meta {
                 Meta::Path(path) if path.is_ident("optionalize_ignore") => {
-                    return (field, true, is_optional);
+                    return (field, true, is_optional, options);
                 }
                 _ => {}
             }
         }
-        return (field, false, is_optional);
+        (field, false, is_optional, options)
+    }).collect();
+    let listed = container_options
+        .exclude
+        .iter()
+        .map(|name| ("exclude", name))
+        .chain(container_options.include.iter().map(|name| ("include", name)));
+    for (attribute, name) in listed {
+        if !fields.iter().any(|(field, _, _, _)| field.ident.as_ref() == Some(name)) {
+            return syn::Error::new_spanned(name, format!("`{}` names unknown field `{}`", attribute, name))
+                .to_compile_error()
+                .into();
+        }
+    }
+    // Fields left out by `exclude` or `include`, and the `sea_orm_timestamp` field, are not
+    // part of the Optional at all; only the code that rebuilds the original struct or an
+    // `ActiveModel` still needs them, through `omitted`.
+    let (fields, omitted): (Vec<_>, Vec<_>) = fields.into_iter().partition(|(field, _, _, options)| {
+        !options.timestamp && field.ident.as_ref().is_none_or(|name| {
+            !container_options.exclude.contains(name)
+                && (container_options.include.is_empty() || container_options.include.contains(name))
+        })
     });
     // Create fields with Option types
-    let optional_fields = fields.clone().map(|(field, is_ignored, is_optional)| {
+    let optional_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
         let field_name = &field.ident;
         let field_type = &field.ty;
+        let rename = serialized_name(field, options, &container_options).map(|rename| {
+            let schemars = if container_options.json_schema {
+                quote! { #[schemars(rename = #rename)] }
+            } else {
+                quote! {}
+            };
+            let graphql = if container_options.graphql {
+                quote! { #[graphql(name = #rename)] }
+            } else {
+                quote! {}
+            };
+            let serde = if container_options.custom_deserializer {
+                quote! {}
+            } else {
+                quote! { #[serde(rename = #rename)] }
+            };
+            quote! {
+                #serde
+                #schemars
+                #graphql
+            }
+        });
+        // `schema_as` replaces the value type; the schema keeps the field's own `Option` wrapping.
+        let schema_as = options.schema_as.as_ref().map(|schema_type| {
+            let with = if is_optional || !is_ignored {
+                quote! { Option<#schema_type> }
+            } else {
+                quote! { #schema_type }
+            };
+            let with = with.to_string();
+            quote! { #[schemars(with = #with)] }
+        });
+        // Every `Option` field becomes an optional (`field?: T`) TypeScript property.
+        let ts_optional = if container_options.typescript && (is_optional || !is_ignored) {
+            quote! { #[ts(optional)] }
+        } else {
+            quote! {}
+        };
+        let vis = if container_options.pub_fields {
+            quote! { pub }
+        } else {
+            let vis = &field.vis;
+            quote! { #vis }
+        };
+        // wasm-bindgen only exposes `Copy` fields directly; everything goes through the
+        // generated accessors instead.
+        let wasm_skip = if container_options.wasm_bindgen {
+            quote! { #[wasm_bindgen(skip)] }
+        } else {
+            quote! {}
+        };
+        let forwarded = field.attrs.iter().filter(|attr| {
+            container_options
+                .forward_attrs
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+        let field_attrs = quote! { #( #forwarded )* #rename #schema_as #wasm_skip };
         match (is_ignored, is_optional) {
-            (false, false) => quote! { #field_name: Option<#field_type> }, // Option<T>
-            (false, true) => quote! { #field_name: #field_type }, // Option<T>
-            (true, false) => quote! { #field_name: #field_type }, // T
-            (true, true) => quote! { #field_name: #field_type}, // Option<T>
+            (false, false) => quote! { #field_attrs #ts_optional #vis #field_name: Option<#field_type> }, // Option<T>
+            (false, true) => quote! { #field_attrs #ts_optional #vis #field_name: #field_type }, // Option<T>
+            (true, false) => quote! { #field_attrs #vis #field_name: #field_type }, // T
+            (true, true) => quote! { #field_attrs #ts_optional #vis #field_name: #field_type}, // Option<T>
+        }
+    });
+
+    let merge_fields = fields.iter().map(|&(ref field, is_ignored, _, _)| {
+        let field_name = &field.ident;
+        if is_ignored {
+            quote! { #field_name: self.#field_name }
+        } else {
+            quote! { #field_name: self.#field_name.or(other.#field_name) }
         }
     });
 
-    let to_active_model_fields = fields.map(|(field, is_ignored, is_optional)| {
+    let patch_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
         let field_name = &field.ident;
         match (is_ignored, is_optional) {
-            (true, false) => {
-                quote! {
-                    #field_name: sea_orm::ActiveValue::Unchanged(self.#field_name)
+            (true, _) => quote! { #field_name: base.#field_name },
+            (false, _) if options.flatten_option => quote! { #field_name: self.#field_name.map(Some).or(base.#field_name) },
+            (false, false) => quote! { #field_name: self.#field_name.unwrap_or(base.#field_name) },
+            (false, true) => quote! { #field_name: self.#field_name.or(base.#field_name) },
+        }
+    }).chain(omitted.iter().map(|(field, _, _, _)| {
+        let field_name = &field.ident;
+        quote! { #field_name: base.#field_name }
+    }));
+
+    let patch_ref_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+        let field_name = &field.ident;
+        match (is_ignored, is_optional) {
+            (true, _) => quote! { #field_name: original.#field_name.clone() },
+            (false, _) if options.flatten_option => {
+                quote! { #field_name: self.#field_name.clone().map(Some).or_else(|| original.#field_name.clone()) }
+            }
+            (false, false) => quote! { #field_name: self.#field_name.clone().unwrap_or_else(|| original.#field_name.clone()) },
+            (false, true) => quote! { #field_name: self.#field_name.clone().or_else(|| original.#field_name.clone()) },
+        }
+    }).chain(omitted.iter().map(|(field, _, _, _)| {
+        let field_name = &field.ident;
+        quote! { #field_name: original.#field_name.clone() }
+    }));
+
+    let shrink_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+        let field_name = &field.ident;
+        match (is_ignored, is_optional) {
+            (true, _) => quote! { #field_name: self.#field_name },
+            (false, _) if options.flatten_option => {
+                quote! { #field_name: self.#field_name.filter(|value| original.#field_name.as_ref().and_then(Option::as_ref) != Some(value)) }
+            }
+            (false, false) => quote! { #field_name: self.#field_name.filter(|value| *value != original.#field_name) },
+            (false, true) => quote! { #field_name: self.#field_name.filter(|value| original.#field_name.as_ref() != Some(value)) },
+        }
+    });
+
+    let update_from_fields = fields.iter().filter(|(_, is_ignored, _, _)| !is_ignored).map(|&(ref field, _, is_optional, ref options)| {
+        let field_name = &field.ident;
+        let value = if is_optional || options.flatten_option { quote! { Some(value) } } else { quote! { value } };
+        quote! {
+            if let Some(value) = patch.#field_name {
+                self.#field_name = #value;
+            }
+        }
+    });
+
+    let from_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+        let field_name = &field.ident;
+        if options.flatten_option {
+            quote! { #field_name: value.#field_name.flatten() }
+        } else if is_ignored || is_optional {
+            quote! { #field_name: value.#field_name }
+        } else {
+            quote! { #field_name: Some(value.#field_name) }
+        }
+    });
+
+    let retain_fields = fields.iter().map(|&(ref field, is_ignored, _, _)| {
+        let field_name = &field.ident;
+        if is_ignored {
+            quote! { #field_name: self.#field_name }
+        } else {
+            let name = field_name.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default();
+            quote! {
+                #field_name: if fields.contains(&#name) { self.#field_name } else { None }
+            }
+        }
+    });
+
+    let transpose_fields: Vec<_> = fields
+        .iter()
+        .filter(|(field, _, _, _)| option_inner_type(&field.ty).and_then(option_inner_type).is_some())
+        .filter_map(|(field, _, _, _)| field.ident.as_ref())
+        .collect();
+
+    let from_hashmap_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+        let field_name = &field.ident;
+        let key = serialized_name(field, options, &container_options)
+            .or_else(|| field_name.as_ref().map(|name| name.unraw().to_string()))
+            .unwrap_or_default();
+        let value_type = if is_optional {
+            option_inner_type(&field.ty).unwrap_or(&field.ty)
+        } else {
+            &field.ty
+        };
+        let parsed = quote! {
+            map.get(#key).and_then(|value| value.parse::<#value_type>().ok())
+        };
+        if is_ignored && !is_optional {
+            quote! { #field_name: #parsed.unwrap_or_default() }
+        } else {
+            quote! { #field_name: #parsed }
+        }
+    });
+
+    let env_prefix = container_options.env_prefix.as_ref().map(syn::LitStr::value).unwrap_or_default();
+    let from_env_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, _)| {
+        let field_name = &field.ident;
+        let variable = format!(
+            "{}{}",
+            env_prefix,
+            field_name.as_ref().map(|name| name.unraw().to_string().to_uppercase()).unwrap_or_default()
+        );
+        let value_type = if is_optional {
+            option_inner_type(&field.ty).unwrap_or(&field.ty)
+        } else {
+            &field.ty
+        };
+        let parsed = quote! {
+            std::env::var(#variable).ok().and_then(|value| value.parse::<#value_type>().ok())
+        };
+        if is_ignored && !is_optional {
+            quote! { #field_name: #parsed.unwrap_or_default() }
+        } else {
+            quote! { #field_name: #parsed }
+        }
+    });
+
+    let to_hashmap_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+        let field_name = &field.ident;
+        let key = serialized_name(field, options, &container_options)
+            .or_else(|| field_name.as_ref().map(|name| name.unraw().to_string()))
+            .unwrap_or_default();
+        if is_ignored && !is_optional {
+            quote! { map.insert(#key, self.#field_name.to_string()); }
+        } else {
+            quote! {
+                if let Some(value) = &self.#field_name {
+                    map.insert(#key, value.to_string());
                 }
-            },
-            (false, false) => {
+            }
+        }
+    });
+
+    let generic_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+        let field_name = &field.ident;
+        let key = serialized_name(field, options, &container_options)
+            .or_else(|| field_name.as_ref().map(|name| name.unraw().to_string()))
+            .unwrap_or_default();
+        let to_value = quote! { ::optionalize::__private::serde_json::to_value(value)? };
+        if is_ignored && !is_optional {
+            quote! {
+                let value = &self.#field_name;
+                fields.insert(#key.to_string(), #to_value);
+            }
+        } else {
+            quote! {
+                if let Some(value) = &self.#field_name {
+                    fields.insert(#key.to_string(), #to_value);
+                }
+            }
+        }
+    });
+
+    let try_complete_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+        let field_name = &field.ident;
+        if options.flatten_option {
+            quote! { #field_name: self.#field_name.map(Some) }
+        } else if is_ignored || is_optional {
+            quote! { #field_name: self.#field_name }
+        } else {
+            let name = field_name.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default();
+            quote! {
+                #field_name: self.#field_name.ok_or(::optionalize::MissingFieldError { field: #name })?
+            }
+        }
+    });
+
+    let required_fields = fields
+        .iter()
+        .filter(|(_, is_ignored, is_optional, _)| !is_ignored && !is_optional)
+        .map(|(field, _, _, _)| &field.ident);
+
+    let sub_fields = fields.iter().map(|&(ref field, is_ignored, _, _)| {
+        let field_name = &field.ident;
+        if is_ignored {
+            quote! { #field_name: self.#field_name }
+        } else {
+            quote! {
+                #field_name: if rhs.#field_name.is_some() { None } else { self.#field_name }
+            }
+        }
+    });
+
+    // Placed on inherent impl blocks only: trait methods never trip `dead_code`.
+    let allow_dead_code = if container_options.allow_dead_code {
+        quote! { #[allow(dead_code)] }
+    } else {
+        quote! {}
+    };
+
+    let builder = if container_options.builder {
+        let builder_name = syn::Ident::new(&format!("{}Builder", optional_struct_name), struct_name.span());
+        let builder_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, _)| {
+            let field_name = &field.ident;
+            let field_type = &field.ty;
+            if is_ignored || is_optional {
+                quote! { #field_name: #field_type }
+            } else {
+                quote! { #field_name: Option<#field_type> }
+            }
+        });
+        let builder_args = fields.iter().filter(|(_, is_ignored, _, _)| *is_ignored).map(|(field, _, _, _)| {
+            let field_name = &field.ident;
+            let field_type = &field.ty;
+            quote! { #field_name: #field_type }
+        });
+        let builder_init = fields.iter().map(|&(ref field, is_ignored, _, _)| {
+            let field_name = &field.ident;
+            if is_ignored {
+                quote! { #field_name }
+            } else {
+                quote! { #field_name: None }
+            }
+        });
+        let setters = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+            let field_name = &field.ident;
+            // Ignored fields are set as declared; everything else is stored as `Some(value)`.
+            let value_type = match (is_ignored, is_optional) {
+                (false, true) => option_inner_type(&field.ty).unwrap_or(&field.ty),
+                _ => &field.ty,
+            };
+            let (param_type, value) = if options.into {
+                (quote! { impl Into<#value_type> }, quote! { value.into() })
+            } else {
+                (quote! { #value_type }, quote! { value })
+            };
+            let assigned = if is_ignored { value } else { quote! { Some(#value) } };
+            quote! {
+                pub fn #field_name(mut self, value: #param_type) -> Self {
+                    self.#field_name = #assigned;
+                    self
+                }
+            }
+        });
+        let build_fields = fields.iter().map(|(field, _, _, _)| {
+            let field_name = &field.ident;
+            quote! { #field_name: self.#field_name }
+        });
+        let build_checked_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+            let field_name = &field.ident;
+            if options.flatten_option {
+                quote! { #field_name: self.#field_name.map(Some) }
+            } else if is_ignored || is_optional {
+                quote! { #field_name: self.#field_name }
+            } else {
+                let name = field_name.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default();
                 quote! {
-                    #field_name: match self.#field_name {
-                        Some(value) => sea_orm::ActiveValue::Set(value),
-                        None => sea_orm::ActiveValue::NotSet
+                    #field_name: self.#field_name.ok_or(::optionalize::MissingFieldError { field: #name })?
+                }
+            }
+        });
+
+        quote! {
+            #[derive(Debug, Clone)]
+            pub struct #builder_name #generics #where_clause {
+                #( #builder_fields, )*
+            }
+
+            #allow_dead_code
+            impl #impl_generics #builder_name #ty_generics #where_clause {
+                pub fn new(#( #builder_args ),*) -> Self {
+                    Self {
+                        #( #builder_init, )*
                     }
                 }
-            },
-            (_, _) => {
-                quote! {
-                    #field_name: match self.#field_name {
-                        Some(value) => sea_orm::ActiveValue::Set(Some(value)),
-                        None => sea_orm::ActiveValue::NotSet
+
+                #( #setters )*
+
+                pub fn build(self) -> #optional_ty {
+                    #optional_struct_name {
+                        #( #build_fields, )*
                     }
                 }
-            },
+
+                /// Builds the original struct, failing on the first required field that was never set.
+                pub fn build_checked(self) -> Result<#struct_ty, ::optionalize::MissingFieldError> {
+                    Ok(#struct_path {
+                        #( #build_checked_fields, )*
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let typestate = if container_options.typestate {
+        let builder_name = syn::Ident::new(&format!("{}TypedBuilder", optional_struct_name), struct_name.span());
+        // One state parameter per required field, appended after the struct's own generics.
+        // Those lose their defaults on the builder, since defaulted parameters must come last.
+        let states: Vec<_> = fields
+            .iter()
+            .filter(|(_, _, _, options)| options.required)
+            .filter_map(|(field, _, _, _)| {
+                let name = field.ident.clone()?;
+                let state = RenameRule::PascalCase.apply_to_field(&name.unraw().to_string());
+                let state = syn::Ident::new(&format!("__{}State", state), name.span());
+                Some((name, state))
+            })
+            .collect();
+        let state_params: Vec<_> = states.iter().map(|(_, state)| state).collect();
+        let mut builder_generics = generics.clone();
+        for param in &mut builder_generics.params {
+            match param {
+                syn::GenericParam::Type(ty) => {
+                    ty.eq_token = None;
+                    ty.default = None;
+                }
+                syn::GenericParam::Const(constant) => {
+                    constant.eq_token = None;
+                    constant.default = None;
+                }
+                syn::GenericParam::Lifetime(_) => {}
+            }
+        }
+        for state in &state_params {
+            builder_generics.params.push(syn::parse_quote! { #state: ::optionalize::FieldState });
+        }
+        let (builder_impl_generics, builder_ty_generics, _) = builder_generics.split_for_impl();
+        let struct_args = generic_args(generics);
+        let builder_ty = |state_args: &[Type]| quote! { #builder_name<#( #struct_args, )* #( #state_args ),*> };
+        let unset_ty = builder_ty(&vec![syn::parse_quote! { ::optionalize::FieldUnset }; states.len()]);
+        let set_ty = builder_ty(&vec![syn::parse_quote! { ::optionalize::FieldSet }; states.len()]);
+
+        let builder_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, _)| {
+            let field_name = &field.ident;
+            let field_type = &field.ty;
+            if is_ignored || is_optional {
+                quote! { #field_name: #field_type }
+            } else {
+                quote! { #field_name: Option<#field_type> }
+            }
+        });
+        let builder_args = fields.iter().filter(|(_, is_ignored, _, _)| *is_ignored).map(|(field, _, _, _)| {
+            let field_name = &field.ident;
+            let field_type = &field.ty;
+            quote! { #field_name: #field_type }
+        });
+        let builder_init = fields.iter().map(|&(ref field, is_ignored, _, _)| {
+            let field_name = &field.ident;
+            if is_ignored {
+                quote! { #field_name }
+            } else {
+                quote! { #field_name: None }
+            }
+        });
+        let field_names: Vec<_> = fields.iter().filter_map(|(field, _, _, _)| field.ident.as_ref()).collect();
+        let setters = fields.iter().filter(|(_, is_ignored, _, _)| !is_ignored).map(|&(ref field, _, is_optional, ref options)| {
+            let field_name = &field.ident;
+            let value_type = if is_optional { option_inner_type(&field.ty).unwrap_or(&field.ty) } else { &field.ty };
+            let (param_type, value) = if options.into {
+                (quote! { impl Into<#value_type> }, quote! { value.into() })
+            } else {
+                (quote! { #value_type }, quote! { value })
+            };
+            if !options.required {
+                return quote! {
+                    pub fn #field_name(mut self, value: #param_type) -> Self {
+                        self.#field_name = Some(#value);
+                        self
+                    }
+                };
+            }
+            // Setting a required field moves the builder into the state where that field is `FieldSet`.
+            let state_args: Vec<_> = states
+                .iter()
+                .map(|(name, state)| if Some(name) == field_name.as_ref() { syn::parse_quote! { ::optionalize::FieldSet } } else { syn::parse_quote! { #state } })
+                .collect();
+            let next_ty = builder_ty(&state_args);
+            let moved = field_names.iter().filter(|name| Some(**name) != field_name.as_ref());
+            quote! {
+                pub fn #field_name(self, value: #param_type) -> #next_ty {
+                    #builder_name {
+                        #field_name: Some(#value),
+                        #( #moved: self.#moved, )*
+                        __state: core::marker::PhantomData,
+                    }
+                }
+            }
+        });
+        let build_fields = field_names.iter().map(|field_name| quote! { #field_name: self.#field_name });
+
+        quote! {
+            /// Builder whose `build()` only exists once every `#[optionalize(required)]` field is set.
+            #[derive(Debug, Clone)]
+            pub struct #builder_name #builder_generics #where_clause {
+                #( #builder_fields, )*
+                __state: core::marker::PhantomData<(#( #state_params, )*)>,
+            }
+
+            #allow_dead_code
+            impl #impl_generics #unset_ty #where_clause {
+                pub fn new(#( #builder_args ),*) -> Self {
+                    Self {
+                        #( #builder_init, )*
+                        __state: core::marker::PhantomData,
+                    }
+                }
+            }
+
+            #allow_dead_code
+            impl #builder_impl_generics #builder_name #builder_ty_generics #where_clause {
+                #( #setters )*
+            }
+
+            #allow_dead_code
+            impl #impl_generics #set_ty #where_clause {
+                pub fn build(self) -> #optional_ty {
+                    #optional_struct_name {
+                        #( #build_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let newtype = container_options.newtype_wrap.as_ref().map(|newtype| {
+        quote! {
+            #[derive(Debug, Clone)]
+            pub struct #newtype #generics (pub #optional_ty) #where_clause;
+
+            impl #impl_generics core::ops::Deref for #newtype #ty_generics #where_clause {
+                type Target = #optional_ty;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl #impl_generics From<#optional_ty> for #newtype #ty_generics #where_clause {
+                fn from(optional: #optional_ty) -> Self {
+                    #newtype(optional)
+                }
+            }
         }
     });
 
-    // Generate the output tokens
-    let expanded = quote! {
+    let required_parts = if let Some(required_struct) = &container_options.required_struct {
+        if !generics.params.is_empty() {
+            return syn::Error::new_spanned(required_struct, "`required_struct` does not support generic structs")
+                .to_compile_error()
+                .into();
+        }
+        let optional_parts = syn::Ident::new(&format!("{}OptionalParts", struct_name), required_struct.span());
+        // Ignored fields are always present, so they travel with the required ones.
+        let (required, optional): (Vec<_>, Vec<_>) =
+            fields.iter().partition(|(_, is_ignored, _, options)| *is_ignored || options.required);
+        let required_defs = required.iter().map(|&&(ref field, is_ignored, is_optional, _)| {
+            let field_name = &field.ident;
+            let field_type = if is_optional && !is_ignored { option_inner_type(&field.ty).unwrap_or(&field.ty) } else { &field.ty };
+            quote! { pub #field_name: #field_type }
+        });
+        let required_init = required.iter().map(|&&(ref field, is_ignored, _, _)| {
+            let field_name = &field.ident;
+            if is_ignored {
+                quote! { #field_name: self.#field_name }
+            } else {
+                let name = field_name.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default();
+                quote! { #field_name: self.#field_name.ok_or(::optionalize::MissingFieldError { field: #name })? }
+            }
+        });
+        let optional_defs = optional.iter().map(|&&(ref field, _, is_optional, _)| {
+            let field_name = &field.ident;
+            let field_type = &field.ty;
+            if is_optional {
+                quote! { pub #field_name: #field_type }
+            } else {
+                quote! { pub #field_name: Option<#field_type> }
+            }
+        });
+        let optional_init = optional.iter().map(|(field, _, _, _)| {
+            let field_name = &field.ident;
+            quote! { #field_name: self.#field_name }
+        });
+        quote! {
+            /// Fields of the Optional that `into_required_parts()` insists on.
+            #[derive(Debug, Clone)]
+            pub struct #required_struct {
+                #( #required_defs, )*
+            }
 
-        #[derive(Debug, Deserialize, Clone)]
-        pub struct #optional_struct_name {
-            #( #optional_fields, )*
+            /// Fields of the Optional that may stay unset.
+            #[derive(Debug, Clone)]
+            pub struct #optional_parts {
+                #( #optional_defs, )*
+            }
+
+            #allow_dead_code
+            impl #optional_struct_name {
+                /// Splits into the `#[optionalize(required)]` (and ignored) fields, failing on
+                /// the first required field that is `None`, and the remaining optional fields.
+                pub fn into_required_parts(self) -> Result<(#required_struct, #optional_parts), ::optionalize::MissingFieldError> {
+                    Ok((
+                        #required_struct {
+                            #( #required_init, )*
+                        },
+                        #optional_parts {
+                            #( #optional_init, )*
+                        },
+                    ))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // With `sea_orm_prelude` the ActiveModel bodies open with `use sea_orm::prelude::*;`.
+    // `ActiveValue` is not part of that prelude, so it is always named by its full path.
+    let sea_orm_prelude = if container_options.sea_orm_prelude {
+        quote! {
+            #[allow(unused_imports)]
+            use ::optionalize::__private::sea_orm::prelude::*;
         }
+    } else {
+        quote! {}
+    };
+    let active_value = quote! { ::optionalize::__private::sea_orm::ActiveValue };
+
+    // `sea_orm_active_model_type` replaces the in-scope `ActiveModel` everywhere it is named.
+    let active_model = match &container_options.sea_orm_active_model_type {
+        Some(path) => quote! { #path },
+        None => quote! { ActiveModel },
+    };
+
+    // `create` fills unset fields with `Default::default()` instead of leaving them `NotSet`.
+    let active_model_fields = |create: bool| {
+        let unset = if create {
+            quote! { #active_value::Set(Default::default()) }
+        } else {
+            quote! { #active_value::NotSet }
+        };
+        let active_value = &active_value;
+        fields.iter().filter(|(_, _, _, options)| !options.skip_in_active).map(move |&(ref field, is_ignored, is_optional, ref options)| {
+            let field_name = &field.ident;
+            let column = active_model_field(field, options);
+            // Inserts store the initial version as given; updates bump it, wrapping around at
+            // the type's maximum so the bumped version still differs from the stored one.
+            if options.version && create {
+                return quote! {
+                    #column: #active_value::Set(self.#field_name)
+                };
+            }
+            if options.version {
+                return quote! {
+                    #column: #active_value::Set(self.#field_name.wrapping_add(1))
+                };
+            }
+            let value_type = if is_optional { option_inner_type(&field.ty).unwrap_or(&field.ty) } else { &field.ty };
+            let non_zero = is_non_zero(value_type);
+            let value = if non_zero { quote! { value.get() } } else { quote! { value } };
+            let value = match &options.active_value_set {
+                Some(transform) => quote! { #transform(#value) },
+                None => value,
+            };
+            match (is_ignored, is_optional) {
+                (true, _) if options.not_set_default => {
+                    quote! {
+                        #column: #active_value::NotSet
+                    }
+                },
+                // Application-generated keys (e.g. UUIDs) are written on insert.
+                (true, false) if create && options.insert_id => {
+                    quote! {
+                        #column: #active_value::Set(self.#field_name)
+                    }
+                },
+                // Other keys are left to the database, e.g. an auto-increment id.
+                (true, false) if create && options.primary_key => {
+                    quote! {
+                        #column: #active_value::NotSet
+                    }
+                },
+                (true, false) => {
+                    quote! {
+                        #column: #active_value::Unchanged(self.#field_name)
+                    }
+                },
+                (false, false) => {
+                    quote! {
+                        #column: match self.#field_name {
+                            Some(value) => #active_value::Set(#value),
+                            None => #unset
+                        }
+                    }
+                },
+                (_, _) => {
+                    quote! {
+                        #column: match self.#field_name {
+                            Some(value) => #active_value::Set(Some(#value)),
+                            None => #unset
+                        }
+                    }
+                },
+            }
+        }).chain(omitted.iter().filter(|(_, _, _, options)| !options.skip_in_active).map(|(field, _, _, options)| {
+            let column = active_model_field(field, options);
+            if options.timestamp {
+                quote! { #column: #active_value::Set(::optionalize::__private::chrono::Utc::now().naive_utc()) }
+            } else {
+                quote! { #column: #active_value::NotSet }
+            }
+        })).collect::<Vec<_>>()
+    };
+    let to_active_model_fields = active_model_fields(false);
+
+    let derive_copy = if container_options.derive_copy {
+        quote! { #[derive(Copy)] }
+    } else {
+        quote! {}
+    };
+
+    // The derives bound every type parameter on the derived trait; a field type that does
+    // not implement it, ignored or not, is reported against that field.
+    let derive_cmp = match (container_options.impl_hash, container_options.impl_ord) {
+        (false, false) => quote! {},
+        (true, false) => quote! { #[derive(PartialEq, Eq, Hash)] },
+        (false, true) => quote! { #[derive(PartialEq, Eq, PartialOrd, Ord)] },
+        (true, true) => quote! { #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)] },
+    };
+
+    let non_exhaustive = if container_options.non_exhaustive {
+        quote! { #[non_exhaustive] }
+    } else {
+        quote! {}
+    };
+
+    let serde_bound = match &container_options.serde_bound {
+        Some(bound) => quote! { #[serde(bound = #bound)] },
+        None => quote! {},
+    };
+
+    let serde_tag = container_options.serde_tag.as_ref().map(|tag| quote! { #[serde(tag = #tag)] });
+    let serde_content = container_options
+        .serde_content
+        .as_ref()
+        .map(|content| quote! { #[serde(content = #content)] });
+    let serde_untagged = container_options.serde_untagged.then(|| quote! { #[serde(untagged)] });
+
+    let graphql = if container_options.graphql {
+        let name = container_options.graphql_input_name.as_ref().map(|name| quote! { #[graphql(name = #name)] });
+        quote! {
+            #[derive(async_graphql::InputObject)]
+            #name
+        }
+    } else {
+        quote! {}
+    };
+
+    let json_schema = if container_options.json_schema {
+        quote! { #[derive(schemars::JsonSchema)] }
+    } else {
+        quote! {}
+    };
+
+    let typescript = if container_options.typescript {
+        quote! { #[derive(ts_rs::TS)] }
+    } else {
+        quote! {}
+    };
+
+    let to_active = if container_options.generates("to_active") && container_options.sea_orm_active_model_behavior {
+        let to_active_model_fields = to_active_model_fields.clone();
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Builds the `ActiveModel` and passes it through
+                /// `ActiveModelBehavior::before_save` as an update.
+                pub async fn to_active<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    self,
+                    db: &C,
+                ) -> Result<#active_model, ::optionalize::__private::sea_orm::DbErr> {
+                    self.to_active_with_hook(db, false).await
+                }
+
+                /// The generated insert helpers build the same `ActiveModel`, but tell
+                /// `before_save` that it is an insert.
+                #[allow(dead_code)]
+                async fn to_active_with_hook<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    self,
+                    db: &C,
+                    insert: bool,
+                ) -> Result<#active_model, ::optionalize::__private::sea_orm::DbErr> {
+                    #sea_orm_prelude
+                    let model = #active_model {
+                        #( #to_active_model_fields, )*
+                    };
+                    <#active_model as ::optionalize::__private::sea_orm::ActiveModelBehavior>::before_save(model, db, insert).await
+                }
+            }
+        }
+    } else if container_options.generates("to_active") {
+        let to_active_model_fields = to_active_model_fields.clone();
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                pub fn to_active(self) -> #active_model {
+                    #sea_orm_prelude
+                    #active_model {
+                        #( #to_active_model_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
 
-        impl #optional_struct_name {
-            pub fn to_active(self) -> ActiveModel {
-                ActiveModel {
-                    #( #to_active_model_fields, )*
+    // `soft_delete()` and `soft_undelete()` differ in the value written to their column and in
+    // whether the keys come from `self` (`receiver`) or are left as they are in `model`.
+    let soft_delete_method = |attribute: &str, column_field: &syn::Ident, method: &str, value: syn::Expr, receiver: bool, doc: &str| {
+        let Some((field, _, _, options)) = fields.iter().find(|(field, _, _, _)| field.ident.as_ref() == Some(column_field)) else {
+            return Err(syn::Error::new_spanned(column_field, format!("`{}` names unknown field `{}`", attribute, column_field)));
+        };
+        if option_inner_type(&field.ty).is_none() {
+            return Err(syn::Error::new_spanned(&field.ty, format!("`{}` requires an `Option` field", attribute)));
+        }
+        let deleted_column = active_model_field(field, options);
+        // Ignored fields stay `Unchanged`, as in `to_active()`, so the update still targets
+        // the right row; fields left out of the Optional have no value to keep.
+        let assignments = fields
+            .iter()
+            .map(|field| (field, false))
+            .chain(omitted.iter().map(|field| (field, true)))
+            .filter(|((field, _, _, options), _)| field.ident.as_ref() != Some(column_field) && !options.skip_in_active)
+            .filter_map(|((field, is_ignored, _, options), is_omitted)| {
+                let field_name = &field.ident;
+                let column = active_model_field(field, options);
+                if *is_ignored && !is_omitted && !options.not_set_default {
+                    receiver.then(|| quote! { model.#column = #active_value::Unchanged(self.#field_name); })
+                } else {
+                    Some(quote! { model.#column = #active_value::NotSet; })
                 }
+            })
+            .collect::<Vec<_>>();
+        let method = syn::Ident::new(method, column_field.span());
+        let receiver = receiver.then(|| quote! { self, });
+        Ok(quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                #[doc = #doc]
+                pub fn #method(#receiver model: &mut #active_model) {
+                    #sea_orm_prelude
+                    model.#deleted_column = #active_value::Set(#value);
+                    #( #assignments )*
+                }
+            }
+        })
+    };
+
+    let soft_delete = match &container_options.sea_orm_soft_delete {
+        Some(deleted_at) if !container_options.sea_orm_skip => {
+            let deleted_type = fields.iter().find(|(field, _, _, _)| field.ident.as_ref() == Some(deleted_at)).map(|(field, _, _, _)| &field.ty);
+            if let Some(ty) = deleted_type.filter(|ty| !option_inner_type(ty).is_some_and(is_naive_date_time)) {
+                return syn::Error::new_spanned(
+                    ty,
+                    "`sea_orm_soft_delete` must be an `Option<NaiveDateTime>` (SeaORM's `DateTime`), which it sets to `Utc::now().naive_utc()`",
+                )
+                .to_compile_error()
+                .into();
+            }
+            match soft_delete_method(
+                "sea_orm_soft_delete",
+                deleted_at,
+                "soft_delete",
+                syn::parse_quote! { Some(::optionalize::__private::chrono::Utc::now().naive_utc()) },
+                true,
+                " Turns `model` into a soft delete: the `sea_orm_soft_delete` column is set to the current time,\n ignored fields such as the primary key are taken from `self` and every other column is left\n untouched.",
+            ) {
+                Ok(method) => method,
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+        _ => quote! {},
+    };
+
+    let soft_undelete = match &container_options.sea_orm_soft_undelete {
+        Some(deleted_at) if !container_options.sea_orm_skip => {
+            match soft_delete_method(
+                "sea_orm_soft_undelete",
+                deleted_at,
+                "soft_undelete",
+                syn::parse_quote! { None },
+                false,
+                " Turns `model` into a restore of a soft-deleted row: the `sea_orm_soft_undelete` column is\n cleared to `None` and every other column is left untouched. There is no Optional to take the\n primary key from, so ignored fields keep whatever `model` holds; build it from the stored row,\n e.g. with `into_active_model()`.",
+            ) {
+                Ok(method) => method,
+                Err(err) => return err.to_compile_error().into(),
             }
         }
+        _ => quote! {},
+    };
 
-        impl OptionalizeTrait for #struct_name {
-            type Optional = #optional_struct_name;
+    // An exhaustive pattern without `..` fails to compile if either side has a field the
+    // other lacks, even when `to_active()` itself is not generated.
+    let deny_extra_fields = if container_options.deny_extra_fields && !container_options.sea_orm_skip {
+        let names = fields
+            .iter()
+            .chain(&omitted)
+            .filter(|(_, _, _, options)| !options.skip_in_active)
+            .map(|(field, _, _, options)| active_model_field(field, options));
+        quote! {
+            const _: fn(#active_model) = |model: #active_model| {
+                let #active_model { #( #names: _, )* } = model;
+            };
         }
+    } else {
+        quote! {}
     };
 
-    TokenStream::from(expanded)
+    // Stable proc macros cannot emit warnings directly; using a `#[deprecated]` item with
+    // the field's span makes rustc report one there instead.
+    let ignored_option_warnings = fields
+        .iter()
+        .filter(|&&(_, is_ignored, is_optional, ref options)| {
+            is_ignored && is_optional && !options.not_set_default && !options.skip_in_active
+        })
+        .filter(|_| container_options.generates("to_active"))
+        .map(|(field, _, _, options)| {
+            let span = syn::spanned::Spanned::span(&field.ty);
+            let name = field.ident.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default();
+            let note = if options.primary_key {
+                format!(
+                    "`#[optionalize(sea_orm_primary_key)]` on the `Option` field `{}`: `to_active()` sets it only when it is `Some` and leaves it `NotSet` otherwise, instead of keeping it `Unchanged`; give the key a non-`Option` type, or add `#[optionalize(sea_orm_not_set_default)]` or `#[optionalize(sea_orm_skip_in_active)]` to say what it should do",
+                    name
+                )
+            } else {
+                format!(
+                    "`#[optionalize(skip)]` on the `Option` field `{}`: `to_active()` sets it only when it is `Some` and leaves it `NotSet` otherwise; if it is the primary key, switch to `#[optionalize(sea_orm_primary_key)]` on a non-`Option` type, otherwise remove the skip, or add `#[optionalize(sea_orm_not_set_default)]` or `#[optionalize(sea_orm_skip_in_active)]` to say what it should do",
+                    name
+                )
+            };
+            quote_spanned! {span=>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    struct IgnoredOptionField;
+                    let _ = IgnoredOptionField;
+                };
+            }
+        });
+
+    // The legacy spelling keeps working during the transition, with a warning at each use.
+    let legacy_ignore_warnings = fields
+        .iter()
+        .flat_map(|(field, _, _, _)| field.attrs.iter().filter(|attr| attr.path().is_ident("optionalize_ignore")))
+        .map(|attr| {
+            let span = syn::spanned::Spanned::span(attr);
+            quote_spanned! {span=>
+                const _: () = {
+                    #[deprecated(note = "`#[optionalize_ignore]` is deprecated; use `#[optionalize(skip)]` instead")]
+                    struct OptionalizeIgnore;
+                    let _ = OptionalizeIgnore;
+                };
+            }
+        });
+
+    let to_create_active = if container_options.sea_orm_default_values && container_options.sea_orm_active_model_behavior && !container_options.sea_orm_skip {
+        let create_fields = active_model_fields(true);
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Like `to_active()`, but for inserts: unset fields are `Set` to their
+                /// `Default::default()` rather than left `NotSet`, and `before_save` is told
+                /// that it is an insert.
+                pub async fn to_create_active<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    self,
+                    db: &C,
+                ) -> Result<#active_model, ::optionalize::__private::sea_orm::DbErr> {
+                    #sea_orm_prelude
+                    let model = #active_model {
+                        #( #create_fields, )*
+                    };
+                    <#active_model as ::optionalize::__private::sea_orm::ActiveModelBehavior>::before_save(model, db, true).await
+                }
+            }
+        }
+    } else if container_options.sea_orm_default_values && !container_options.sea_orm_skip {
+        let create_fields = active_model_fields(true);
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Like `to_active()`, but for inserts: unset fields are `Set` to their
+                /// `Default::default()` rather than left `NotSet`.
+                pub fn to_create_active(self) -> #active_model {
+                    #sea_orm_prelude
+                    #active_model {
+                        #( #create_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // The `ActiveModel` the generated insert helpers write, built from `receiver`.
+    let insert_active = |receiver: &str| {
+        let receiver = syn::Ident::new(receiver, struct_name.span());
+        match (container_options.sea_orm_default_values, container_options.sea_orm_active_model_behavior) {
+            (true, true) => quote! { #receiver.to_create_active(db).await? },
+            (true, false) => quote! { #receiver.to_create_active() },
+            (false, true) => quote! { #receiver.to_active_with_hook(db, true).await? },
+            (false, false) => quote! { #receiver.to_active() },
+        }
+    };
+
+    let column_types: Vec<_> = fields
+        .iter()
+        .filter_map(|(field, _, _, options)| {
+            let column_type = options.column_type.as_ref()?;
+            let name = field.ident.as_ref()?.unraw().to_string();
+            Some(quote! { (#name, ::optionalize::__private::sea_orm::ColumnType::#column_type) })
+        })
+        .collect();
+    let column_types = if !container_options.sea_orm_skip && !column_types.is_empty() {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Column types declared with `#[optionalize(sea_orm_column_type = "...")]`, by field name.
+                pub fn column_types() -> Vec<(&'static str, ::optionalize::__private::sea_orm::ColumnType)> {
+                    vec![ #( #column_types, )* ]
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Column variants follow `DeriveEntityModel`: the field name in PascalCase.
+    let entity_filters: Vec<_> = fields.iter().filter(|(_, is_ignored, _, options)| !is_ignored && !options.skip_in_active).map(|&(ref field, _, is_optional, ref options)| {
+        let field_name = &field.ident;
+        let column = active_model_field(field, options).map(|name| {
+            let column = RenameRule::PascalCase.apply_to_field(&name.unraw().to_string());
+            syn::Ident::new(&column, name.span())
+        });
+        let value_type = if is_optional { option_inner_type(&field.ty).unwrap_or(&field.ty) } else { &field.ty };
+        let value = if is_non_zero(value_type) { quote! { value.get() } } else { quote! { value } };
+        quote! {
+            if let Some(value) = filter.#field_name {
+                query = query.filter(Column::#column.eq(#value));
+            }
+        }
+    }).collect();
+
+    let find_by = if container_options.sea_orm_find_by && !container_options.sea_orm_skip {
+        let finder = |name: &str, lock: bool| {
+            let name = syn::Ident::new(name, struct_name.span());
+            let (doc, lock) = if lock {
+                (
+                    "Like `find_by_shared()`, but locks the matched rows with `SELECT ... FOR UPDATE`\nfor a read-modify-write inside a transaction.",
+                    quote! { let query = ::optionalize::__private::sea_orm::QuerySelect::lock_exclusive(query); },
+                )
+            } else {
+                (
+                    "Loads every row of `Entity` matching all fields set on `self`; unset and\nignored fields do not filter.",
+                    quote! {},
+                )
+            };
+            quote! {
+                #[doc = #doc]
+                pub async fn #name<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    self,
+                    db: &C,
+                ) -> Result<Vec<<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model>, ::optionalize::__private::sea_orm::DbErr> {
+                    use ::optionalize::__private::sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+                    let filter = self;
+                    let mut query = Entity::find();
+                    #( #entity_filters )*
+                    #lock
+                    query.all(db).await
+                }
+            }
+        };
+        let methods = if container_options.sea_orm_exclusive_lock {
+            let locked = finder("find_by", true);
+            let shared = finder("find_by_shared", false);
+            quote! { #locked #shared }
+        } else {
+            finder("find_by", false)
+        };
+        let with_related = container_options.sea_orm_preload.as_ref().map(|related| {
+            quote! {
+                /// Like `find_by()`, but also loads the rows of the `sea_orm_preload` entity
+                /// related to each match, with `find_with_related`.
+                pub async fn find_by_with_related<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    self,
+                    db: &C,
+                ) -> Result<
+                    Vec<(<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model, Vec<<#related as ::optionalize::__private::sea_orm::EntityTrait>::Model>)>,
+                    ::optionalize::__private::sea_orm::DbErr,
+                > {
+                    use ::optionalize::__private::sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+                    let filter = self;
+                    let mut query = Entity::find().find_with_related(#related);
+                    #( #entity_filters )*
+                    query.all(db).await
+                }
+            }
+        });
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                #methods
+                #with_related
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let save = if container_options.sea_orm_with_transaction && container_options.generates("to_active") {
+        let to_active = if container_options.sea_orm_active_model_behavior {
+            quote! { self.to_active(txn).await? }
+        } else {
+            quote! { self.to_active() }
+        };
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Saves `to_active()` inside a transaction and returns the stored model; SeaORM
+                /// inserts when the primary key is `NotSet` and updates otherwise.
+                pub async fn save<C: ::optionalize::__private::sea_orm::TransactionTrait>(
+                    self,
+                    db: &C,
+                ) -> Result<<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model, ::optionalize::__private::sea_orm::DbErr> {
+                    use ::optionalize::__private::sea_orm::{ActiveModelTrait, TryIntoModel};
+                    db.transaction::<_, _, ::optionalize::__private::sea_orm::DbErr>(|txn| {
+                        Box::pin(async move {
+                            let model = #to_active;
+                            model.save(txn).await?.try_into_model()
+                        })
+                    })
+                    .await
+                    .map_err(|err| match err {
+                        ::optionalize::__private::sea_orm::TransactionError::Connection(err) | ::optionalize::__private::sea_orm::TransactionError::Transaction(err) => err,
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let find_or_create = if container_options.sea_orm_find_or_create && !container_options.sea_orm_skip {
+        let primary_keys: Vec<_> = fields.iter().filter(|(_, _, _, options)| options.primary_key).collect();
+        let [(primary_key, _, _, pk_options)] = primary_keys.as_slice() else {
+            return syn::Error::new(
+                struct_name.span(),
+                "`sea_orm_find_or_create` requires exactly one `#[optionalize(sea_orm_primary_key)]` field",
+            )
+            .to_compile_error()
+            .into();
+        };
+        let pk_column = active_model_field(primary_key, pk_options);
+        let pk_type = &primary_key.ty;
+        let create = insert_active("defaults");
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Loads the row with primary key `id`, or inserts `defaults` under that key
+                /// when there is none. The flag is `true` when the row was created.
+                pub async fn find_or_create<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    db: &C,
+                    id: #pk_type,
+                    defaults: Self,
+                ) -> Result<(<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model, bool), ::optionalize::__private::sea_orm::DbErr> {
+                    use ::optionalize::__private::sea_orm::{ActiveModelTrait, EntityTrait};
+                    if let Some(model) = Entity::find_by_id(id.clone()).one(db).await? {
+                        return Ok((model, false));
+                    }
+                    // Set explicitly: an insert leaves keys without `sea_orm_insert_id` unset.
+                    let mut active = #create;
+                    active.#pk_column = ::optionalize::__private::sea_orm::ActiveValue::Set(id);
+                    Ok((active.insert(db).await?, true))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let batch_update = if container_options.sea_orm_batch_update && container_options.generates("to_active") {
+        let primary_keys: Vec<_> = fields.iter().filter(|(_, _, _, options)| options.primary_key).collect();
+        let [(primary_key, _, _, pk_options)] = primary_keys.as_slice() else {
+            return syn::Error::new(
+                struct_name.span(),
+                "`sea_orm_batch_update` requires exactly one `#[optionalize(sea_orm_primary_key)]` field",
+            )
+            .to_compile_error()
+            .into();
+        };
+        let pk_type = &primary_key.ty;
+        let pk_column = active_model_field(primary_key, pk_options).map(|name| {
+            let column = RenameRule::PascalCase.apply_to_field(&name.unraw().to_string());
+            syn::Ident::new(&column, name.span())
+        });
+        let to_active = if container_options.sea_orm_active_model_behavior {
+            quote! { patch.to_active(db).await? }
+        } else {
+            quote! { patch.to_active() }
+        };
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Applies the fields set on `patch` to every row whose primary key is in `ids`
+                /// with a single `UPDATE`, returning the number of rows affected. The primary
+                /// key on `patch` itself is not used.
+                pub async fn batch_update<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    db: &C,
+                    ids: Vec<#pk_type>,
+                    patch: Self,
+                ) -> Result<u64, ::optionalize::__private::sea_orm::DbErr> {
+                    use ::optionalize::__private::sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+                    let result = Entity::update_many()
+                        .set(#to_active)
+                        .filter(Column::#pk_column.is_in(ids))
+                        .exec(db)
+                        .await?;
+                    Ok(result.rows_affected)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let returning = if container_options.sea_orm_returning && container_options.generates("to_active") {
+        let primary_keys: Vec<_> = fields.iter().filter(|(_, _, _, options)| options.primary_key).collect();
+        let [(primary_key, _, _, _)] = primary_keys.as_slice() else {
+            return syn::Error::new(
+                struct_name.span(),
+                "`sea_orm_returning` requires exactly one `#[optionalize(sea_orm_primary_key)]` field",
+            )
+            .to_compile_error()
+            .into();
+        };
+        let pk_name = &primary_key.ident;
+        let pk_type = &primary_key.ty;
+        let to_active = if container_options.sea_orm_active_model_behavior {
+            quote! { self.to_active(db).await? }
+        } else {
+            quote! { self.to_active() }
+        };
+        let create = insert_active("self");
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Inserts `self` and returns the stored row, read back with `RETURNING` where
+                /// the backend supports it.
+                pub async fn insert_and_return<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    self,
+                    db: &C,
+                ) -> Result<<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model, ::optionalize::__private::sea_orm::DbErr> {
+                    use ::optionalize::__private::sea_orm::EntityTrait;
+                    Entity::insert(#create).exec_with_returning(db).await
+                }
+
+                /// Updates the row with primary key `id` with the fields set on `self` and
+                /// returns it as stored, read back with `RETURNING` where the backend supports it.
+                pub async fn update_and_return<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    mut self,
+                    id: #pk_type,
+                    db: &C,
+                ) -> Result<<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model, ::optionalize::__private::sea_orm::DbErr> {
+                    use ::optionalize::__private::sea_orm::EntityTrait;
+                    self.#pk_name = id;
+                    Entity::update(#to_active).exec(db).await
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let check_version = match &container_options.sea_orm_version_column {
+        Some(version) if !container_options.sea_orm_skip => {
+            let primary_keys: Vec<_> = fields.iter().filter(|(_, _, _, options)| options.primary_key).collect();
+            let [(primary_key, _, _, _)] = primary_keys.as_slice() else {
+                return syn::Error::new_spanned(version, "`sea_orm_version_column` requires exactly one `#[optionalize(sea_orm_primary_key)]` field")
+                    .to_compile_error()
+                    .into();
+            };
+            let pk_name = &primary_key.ident;
+            let pk_type = &primary_key.ty;
+            let (version_field, _, _, version_options) = fields
+                .iter()
+                .find(|(_, _, _, options)| options.version)
+                .expect("the version field was marked above");
+            let version_name = &version_field.ident;
+            let version_type = &version_field.ty;
+            let version_column = active_model_field(version_field, version_options);
+            let version_variant = version_column.as_ref().map(|name| {
+                let column = RenameRule::PascalCase.apply_to_field(&name.unraw().to_string());
+                syn::Ident::new(&column, name.span())
+            });
+            let to_active = if container_options.sea_orm_active_model_behavior {
+                quote! { self.to_active(db).await.map_err(::optionalize::OptimisticLockError::Database)? }
+            } else {
+                quote! { self.to_active() }
+            };
+            quote! {
+                #allow_dead_code
+                impl #impl_generics #optional_ty #where_clause {
+                    /// Loads the row with primary key `id` and checks that its version is still
+                    /// `expected_version`, i.e. that nobody updated it since it was read. The check
+                    /// is advisory: an update issued afterwards can still race with another writer,
+                    /// unless it goes through `update_with_version_check()`.
+                    pub async fn find_and_check_version<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                        db: &C,
+                        id: #pk_type,
+                        expected_version: #version_type,
+                    ) -> Result<<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model, ::optionalize::OptimisticLockError<::optionalize::__private::sea_orm::DbErr>> {
+                        use ::optionalize::__private::sea_orm::EntityTrait;
+                        let model = Entity::find_by_id(id)
+                            .one(db)
+                            .await
+                            .map_err(::optionalize::OptimisticLockError::Database)?
+                            .ok_or(::optionalize::OptimisticLockError::NotFound)?;
+                        if model.#version_column != expected_version {
+                            return Err(::optionalize::OptimisticLockError::VersionMismatch {
+                                expected: i64::from(expected_version),
+                                found: i64::from(model.#version_column),
+                            });
+                        }
+                        Ok(model)
+                    }
+
+                    /// Writes `to_active()` with an `UPDATE` that only matches the row if its
+                    /// version is still the one on `self`, so of two writers that read the same
+                    /// version only the first succeeds. Returns the stored row, or
+                    /// `VersionMismatch`/`NotFound` when nothing was updated.
+                    pub async fn update_with_version_check<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                        self,
+                        db: &C,
+                    ) -> Result<<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model, ::optionalize::OptimisticLockError<::optionalize::__private::sea_orm::DbErr>> {
+                        use ::optionalize::__private::sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+                        let id = self.#pk_name.clone();
+                        let expected_version = self.#version_name;
+                        let active = #to_active;
+                        match Entity::update(active).filter(Column::#version_variant.eq(expected_version)).exec(db).await {
+                            Ok(model) => Ok(model),
+                            Err(::optionalize::__private::sea_orm::DbErr::RecordNotUpdated) => {
+                                Self::find_and_check_version(db, id, expected_version).await?;
+                                // The row matched after all, so it changed back in between.
+                                Err(::optionalize::OptimisticLockError::Database(::optionalize::__private::sea_orm::DbErr::RecordNotUpdated))
+                            }
+                            Err(err) => Err(::optionalize::OptimisticLockError::Database(err)),
+                        }
+                    }
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
+    let paginate = if container_options.sea_orm_pagination && !container_options.sea_orm_skip {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Loads page `page` (zero-based, `per_page` rows each) of the `Entity` rows
+                /// matching every field set on `filter`, using SeaORM's `Paginator`.
+                pub async fn paginate<C: ::optionalize::__private::sea_orm::ConnectionTrait>(
+                    db: &C,
+                    filter: Self,
+                    page: u64,
+                    per_page: u64,
+                ) -> Result<Vec<<Entity as ::optionalize::__private::sea_orm::EntityTrait>::Model>, ::optionalize::__private::sea_orm::DbErr> {
+                    use ::optionalize::__private::sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+                    let mut query = Entity::find();
+                    #( #entity_filters )*
+                    query.paginate(db, per_page).fetch_page(page).await
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let into_active_model = if container_options.sea_orm_into_active_model {
+        quote! {
+            impl #impl_generics ::optionalize::__private::sea_orm::IntoActiveModel<#active_model> for #optional_ty #where_clause {
+                fn into_active_model(self) -> #active_model {
+                    #sea_orm_prelude
+                    #active_model {
+                        #( #to_active_model_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let constrained_patch = if container_options.constrained_patch {
+        // Bounded through references so that a field type that is not `Debug` only fails
+        // at the call site rather than rejecting the whole impl.
+        let mut debug_generics = generics.clone();
+        let validations = fields
+            .iter()
+            .filter(|(field, is_ignored, _, _)| {
+                !is_ignored && field.ident.as_ref().is_none_or(|name| !container_options.debug_redact.contains(name))
+            })
+            .map(|&(ref field, _, is_optional, _)| {
+                let field_name = &field.ident;
+                let value_type = if is_optional { option_inner_type(&field.ty).unwrap_or(&field.ty) } else { &field.ty };
+                debug_generics.make_where_clause().predicates.push(syn::parse_quote! { for<'a> &'a #value_type: core::fmt::Debug });
+                let name = field_name.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default();
+                quote! {
+                    if let Some(value) = &self.#field_name {
+                        validate(#name, &value)?;
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let debug_where_clause = &debug_generics.where_clause;
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #debug_where_clause {
+                /// Like `patch()`, but first passes every set field's name and value to `validate`.
+                /// The first error is returned before anything is patched. Fields listed in
+                /// `impl_debug_redact` are not passed.
+                pub fn constrained_patch<E>(
+                    self,
+                    base: #struct_ty,
+                    validate: impl Fn(&str, &dyn core::fmt::Debug) -> Result<(), E>,
+                ) -> Result<#struct_ty, E> {
+                    #( #validations )*
+                    Ok(self.patch(base))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let patch = if container_options.generates("patch") {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Applies this patch to `base`: fields set on `self` replace the base value,
+                /// everything else (including ignored fields) is taken from `base`.
+                pub fn patch(self, base: #struct_ty) -> #struct_ty {
+                    #struct_path {
+                        #( #patch_fields, )*
+                    }
+                }
+            }
+
+            impl #impl_generics core::ops::Add<#optional_ty> for #struct_ty #where_clause {
+                type Output = #struct_ty;
+
+                /// `base + patch` is shorthand for `patch.patch(base)`.
+                fn add(self, patch: #optional_ty) -> #struct_ty {
+                    patch.patch(self)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let patch_ref = if container_options.clone_on_none {
+        let mut clone_generics = generics.clone();
+        for (field, _, _, _) in fields.iter().chain(&omitted) {
+            let field_type = &field.ty;
+            clone_generics.make_where_clause().predicates.push(syn::parse_quote! { #field_type: Clone });
+        }
+        let clone_where_clause = &clone_generics.where_clause;
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #clone_where_clause {
+                /// Like `patch()`, but borrows both sides and clones: fields set on `self` are
+                /// cloned from `self`, everything else from `original`.
+                pub fn patch_ref(&self, original: &#struct_ty) -> #struct_ty {
+                    #struct_path {
+                        #( #patch_ref_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let shrink = if container_options.shrink {
+        let mut eq_generics = generics.clone();
+        for (field, _, _, _) in &fields {
+            let field_type = &field.ty;
+            eq_generics.make_where_clause().predicates.push(syn::parse_quote! { #field_type: PartialEq });
+        }
+        let eq_where_clause = &eq_generics.where_clause;
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #eq_where_clause {
+                /// Unsets every field whose value equals the one on `original`, leaving only
+                /// the actual changes. Ignored fields are kept.
+                pub fn shrink(self, original: &#struct_ty) -> Self {
+                    Self {
+                        #( #shrink_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let update_from = if container_options.impl_update_from {
+        if foreign {
+            return syn::Error::new_spanned(&struct_path, "`impl_update_from` is not available for structs from other crates")
+                .to_compile_error()
+                .into();
+        }
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #struct_ty #where_clause {
+                /// Updates `self` in place: fields set on `patch` replace the current value,
+                /// everything else (including ignored fields) is left unchanged.
+                pub fn update_from(&mut self, patch: #optional_ty) {
+                    #( #update_from_fields )*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let snapshot = if container_options.impl_snapshot {
+        let snapshot_fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+            let field_name = &field.ident;
+            if options.flatten_option {
+                quote! { #field_name: original.#field_name.clone().flatten() }
+            } else if is_ignored || is_optional {
+                quote! { #field_name: original.#field_name.clone() }
+            } else {
+                quote! { #field_name: Some(original.#field_name.clone()) }
+            }
+        });
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Captures the current state of `original`, with every field set, e.g. to
+                /// compare against later. Clones each field.
+                pub fn snapshot(original: &#struct_ty) -> Self {
+                    Self {
+                        #( #snapshot_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let from_model = match &container_options.sea_orm_model {
+        Some(model) if !container_options.sea_orm_skip => {
+            let from_fields = from_fields.clone();
+            quote! {
+                #allow_dead_code
+                impl #impl_generics #optional_ty #where_clause {
+                    /// Builds the Optional from the SeaORM read model, with every field set.
+                    /// The model must have a field of the same name and type for each field here.
+                    pub fn from_model(value: #model) -> Self {
+                        Self {
+                            #( #from_fields, )*
+                        }
+                    }
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
+    let from = if container_options.generates("from") {
+        let as_optional = if foreign {
+            quote! {}
+        } else {
+            quote! {
+                #allow_dead_code
+                impl #impl_generics #struct_ty #where_clause {
+                    /// Converts into the Optional with every field set; same as `Optional::from(self)`.
+                    pub fn as_optional(self) -> #optional_ty {
+                        #optional_struct_name::from(self)
+                    }
+                }
+            }
+        };
+        quote! {
+            impl #impl_generics From<#struct_ty> for #optional_ty #where_clause {
+                fn from(value: #struct_ty) -> Self {
+                    Self {
+                        #( #from_fields, )*
+                    }
+                }
+            }
+
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Wraps every field of `value` in `Some`; same as `Self::from(value)`.
+                pub fn from_struct(value: #struct_ty) -> Self {
+                    Self::from(value)
+                }
+            }
+
+            #as_optional
+        }
+    } else {
+        quote! {}
+    };
+
+    let from_hashmap = if container_options.impl_from_hashmap {
+        quote! {
+            impl #impl_generics From<std::collections::HashMap<String, String>> for #optional_ty #where_clause {
+                /// Parses every known key with `str::parse`; missing or unparseable values become `None`
+                /// (ignored fields fall back to `Default::default()`).
+                fn from(map: std::collections::HashMap<String, String>) -> Self {
+                    Self {
+                        #( #from_hashmap_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let from_env = if container_options.from_env {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Reads each field from the environment variable named after it in upper case,
+                /// behind the configured prefix; unset or unparseable variables become `None`
+                /// (ignored fields fall back to `Default::default()`).
+                pub fn from_env() -> Self {
+                    Self {
+                        #( #from_env_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let to_hashmap = if container_options.to_hashmap {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Renders every set field with `ToString`, keyed by its serialized name.
+                pub fn to_hashmap(&self) -> std::collections::HashMap<&'static str, String> {
+                    let mut map = std::collections::HashMap::new();
+                    #( #to_hashmap_fields )*
+                    map
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Emitted for every Optional once the `json` feature is on, like `Deserialize` itself.
+    let try_from_json = if cfg!(feature = "json") && !container_options.no_std {
+        let mut json_generics = generics.clone();
+        json_generics.make_where_clause().predicates.push(syn::parse_quote! { #optional_ty: for<'de> ::optionalize::__private::serde::Deserialize<'de> });
+        let json_where_clause = &json_generics.where_clause;
+        quote! {
+            impl #impl_generics TryFrom<&::optionalize::__private::serde_json::Value> for #optional_ty #json_where_clause {
+                type Error = ::optionalize::__private::serde_json::Error;
+
+                /// Reads the fields present in a JSON object; absent fields stay `None` and
+                /// unknown keys are skipped, but a known field of the wrong type is an error.
+                fn try_from(value: &::optionalize::__private::serde_json::Value) -> Result<Self, Self::Error> {
+                    <Self as ::optionalize::__private::serde::Deserialize>::deserialize(value)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // The bounds are higher-ranked so that a field type without `Serialize` only removes
+    // this impl rather than failing the derive: rustc rejects an unsatisfiable bound on a
+    // concrete type, but not one quantified over a lifetime.
+    let into_json = if cfg!(feature = "json") && !container_options.no_std {
+        let mut json_generics = generics.clone();
+        let json_where = json_generics.make_where_clause();
+        let entries = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+            let field_name = &field.ident;
+            let key = serialized_name(field, options, &container_options)
+                .or_else(|| field_name.as_ref().map(|name| name.unraw().to_string()))
+                .unwrap_or_default();
+            let value_type = if is_optional {
+                option_inner_type(&field.ty).unwrap_or(&field.ty)
+            } else {
+                &field.ty
+            };
+            json_where.predicates.push(syn::parse_quote! { for<'a> &'a #value_type: ::optionalize::__private::serde::Serialize });
+            let to_value = quote! {
+                ::optionalize::__private::serde_json::to_value(value).unwrap_or(::optionalize::__private::serde_json::Value::Null)
+            };
+            if is_ignored && !is_optional {
+                quote! {
+                    let value = &optional.#field_name;
+                    object.insert(#key.to_string(), #to_value);
+                }
+            } else {
+                quote! {
+                    if let Some(value) = &optional.#field_name {
+                        object.insert(#key.to_string(), #to_value);
+                    }
+                }
+            }
+        }).collect::<Vec<_>>();
+        let json_where_clause = &json_generics.where_clause;
+        quote! {
+            impl #impl_generics From<#optional_ty> for ::optionalize::__private::serde_json::Value #json_where_clause {
+                /// A JSON object holding only the set fields, keyed by serialized name; unlike
+                /// serializing the Optional, unset fields are left out rather than `null`.
+                /// A set field whose `Serialize` impl fails, e.g. a map with non-string keys,
+                /// is written as `null` instead of panicking.
+                fn from(optional: #optional_ty) -> Self {
+                    let mut object = ::optionalize::__private::serde_json::Map::new();
+                    #( #entries )*
+                    ::optionalize::__private::serde_json::Value::Object(object)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let generic_optional = if container_options.generic_optional {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Serializes every set field into JSON, keyed by its serialized name. Fails if a
+                /// field's `Serialize` impl does, e.g. for a map with non-string keys.
+                pub fn into_generic(
+                    self,
+                ) -> Result<::optionalize::GenericOptional<::optionalize::__private::serde_json::Value>, ::optionalize::__private::serde_json::Error> {
+                    let mut fields = std::collections::HashMap::new();
+                    #( #generic_fields )*
+                    Ok(::optionalize::GenericOptional { fields })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Without the left-out fields there is nothing to complete the original struct from.
+    let try_complete = if container_options.generates("try_complete") && omitted.is_empty() {
+        let (error, body) = if container_options.accumulate_errors {
+            let required = required_fields.clone().collect::<Vec<_>>();
+            let required_names = required
+                .iter()
+                .map(|field_name| field_name.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default());
+            let fields = fields.iter().map(|&(ref field, is_ignored, is_optional, ref options)| {
+                let field_name = &field.ident;
+                if options.flatten_option {
+                    quote! { #field_name: self.#field_name.map(Some) }
+                } else if is_ignored || is_optional {
+                    quote! { #field_name: self.#field_name }
+                } else {
+                    quote! { #field_name }
+                }
+            });
+            let check = if required.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    let mut __missing = Vec::new();
+                    #(
+                        let #required = self.#required;
+                        if #required.is_none() {
+                            __missing.push(#required_names);
+                        }
+                    )*
+                    let ( #( Some(#required), )* ) = ( #( #required, )* ) else {
+                        return Err(::optionalize::MissingFieldsError { fields: __missing });
+                    };
+                }
+            };
+            let body = quote! {
+                #check
+                Ok(#struct_path {
+                    #( #fields, )*
+                })
+            };
+            (quote! { ::optionalize::MissingFieldsError }, body)
+        } else {
+            let body = quote! {
+                Ok(#struct_path {
+                    #( #try_complete_fields, )*
+                })
+            };
+            (quote! { ::optionalize::MissingFieldError }, body)
+        };
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Whether every field required by the original struct is set.
+                pub fn is_complete(&self) -> bool {
+                    true #( && self.#required_fields.is_some() )*
+                }
+
+                /// Converts back into the original struct, reporting the required fields that are `None`.
+                pub fn try_complete(self) -> Result<#struct_ty, #error> {
+                    #body
+                }
+            }
+
+            impl #impl_generics TryFrom<#optional_ty> for #struct_ty #where_clause {
+                type Error = #error;
+
+                fn try_from(value: #optional_ty) -> Result<Self, Self::Error> {
+                    value.try_complete()
+                }
+            }
+
+            impl #impl_generics From<#optional_ty> for Option<#struct_ty> #where_clause {
+                /// `Some` when every required field is set, `None` otherwise.
+                fn from(value: #optional_ty) -> Self {
+                    value.try_complete().ok()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let merge = if container_options.generates("merge") {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Fills every field left as `None` on `self` with the value from `other`.
+                /// Ignored fields always keep the value from `self`.
+                pub fn merge(self, other: Self) -> Self {
+                    Self {
+                        #( #merge_fields, )*
+                    }
+                }
+            }
+
+            impl #impl_generics ::optionalize::OptionalizeMerge for #optional_ty #where_clause {
+                fn merge(self, rhs: Self) -> Self {
+                    #optional_struct_name::merge(self, rhs)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // The values are the names `retain_fields()` matches against.
+    let constants = if container_options.generate_constants {
+        let constants = fields.iter().filter_map(|(field, _, _, _)| {
+            let ident = field.ident.as_ref()?;
+            let name = ident.unraw().to_string();
+            let constant = syn::Ident::new(&format!("FIELD_NAME_{}", name.to_uppercase()), ident.span());
+            Some(quote! { pub const #constant: &'static str = #name; })
+        });
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                #( #constants )*
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let retain = if container_options.generates("retain_fields") {
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                /// Keeps only the fields named in `fields` and sets every other field to `None`.
+                /// Ignored fields are always retained.
+                pub fn retain_fields(self, fields: &[&str]) -> Self {
+                    Self {
+                        #( #retain_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let transpose = if container_options.generates("transpose") && !transpose_fields.is_empty() {
+        let methods = transpose_fields.iter().map(|field_name| {
+            let method = syn::Ident::new(&format!("transpose_{}", field_name.unraw()), field_name.span());
+            quote! {
+                /// Collapses `Some(None)` in this field to `None`.
+                pub fn #method(self) -> Self {
+                    Self { #field_name: self.#field_name.flatten().map(Some), ..self }
+                }
+            }
+        });
+        let calls = transpose_fields
+            .iter()
+            .map(|field_name| syn::Ident::new(&format!("transpose_{}", field_name.unraw()), field_name.span()));
+        quote! {
+            #allow_dead_code
+            impl #impl_generics #optional_ty #where_clause {
+                #( #methods )*
+
+                /// Collapses `Some(None)` to `None` in every `Option<Option<T>>` field.
+                pub fn transpose_all(self) -> Self {
+                    self #( .#calls() )*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Written out rather than derived: only ignored fields need their type to be `Default`.
+    // Spanned at the field type, so a type that is not `Default` is reported there.
+    let default_fields = fields.iter().map(|&(ref field, is_ignored, _, _)| {
+        let field_name = &field.ident;
+        if is_ignored {
+            let field_type = &field.ty;
+            let span = syn::spanned::Spanned::span(field_type);
+            quote_spanned! {span=> #field_name: <#field_type as Default>::default() }
+        } else {
+            quote! { #field_name: None }
+        }
+    });
+    // A bound on a concrete type is rejected outright when it does not hold, so only types
+    // that depend on a parameter are bounded.
+    let mut default_generics = generics.clone();
+    for (field, _, _, _) in fields.iter().filter(|(field, is_ignored, _, _)| *is_ignored && mentions_generic_param(&field.ty, generics)) {
+        let field_type = &field.ty;
+        default_generics.make_where_clause().predicates.push(syn::parse_quote! { #field_type: Default });
+    }
+    let default_where_clause = &default_generics.where_clause;
+    let default = quote! {
+        impl #impl_generics Default for #optional_ty #default_where_clause {
+            /// An Optional with every field unset; ignored fields take their `Default`.
+            fn default() -> Self {
+                Self {
+                    #( #default_fields, )*
+                }
+            }
+        }
+    };
+
+    let partial_eq = match &container_options.partial_eq_ignoring {
+        Some(ignored) => {
+            if let Some(unknown) = ignored.iter().find(|name| !fields.iter().any(|(field, _, _, _)| field.ident.as_ref() == Some(*name))) {
+                return syn::Error::new_spanned(unknown, format!("`partial_eq_ignoring` names unknown field `{}`", unknown))
+                    .to_compile_error()
+                    .into();
+            }
+            let compared: Vec<_> = fields
+                .iter()
+                .filter(|(field, _, _, _)| !field.ident.as_ref().is_some_and(|name| ignored.contains(name)))
+                .collect();
+            let mut eq_generics = generics.clone();
+            for (field, is_ignored, is_optional, _) in &compared {
+                let field_type = &field.ty;
+                let predicate = if *is_ignored || *is_optional {
+                    syn::parse_quote! { #field_type: PartialEq }
+                } else {
+                    syn::parse_quote! { Option<#field_type>: PartialEq }
+                };
+                eq_generics.make_where_clause().predicates.push(predicate);
+            }
+            let eq_where_clause = &eq_generics.where_clause;
+            let compared_names = compared.iter().map(|(field, _, _, _)| &field.ident);
+            quote! {
+                impl #impl_generics PartialEq for #optional_ty #eq_where_clause {
+                    /// Compares every field except the ones listed in `partial_eq_ignoring`.
+                    fn eq(&self, other: &Self) -> bool {
+                        true #( && self.#compared_names == other.#compared_names )*
+                    }
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    let redacted = &container_options.debug_redact;
+    if let Some(unknown) = redacted.iter().find(|name| !fields.iter().any(|(field, _, _, _)| field.ident.as_ref() == Some(*name))) {
+        return syn::Error::new_spanned(unknown, format!("`impl_debug_redact` names unknown field `{}`", unknown))
+            .to_compile_error()
+            .into();
+    }
+    let (derive_debug, compact_debug) = if container_options.compact_debug || !redacted.is_empty() {
+        let debug_fields = fields.iter().map(|&(ref field, is_ignored, _, _)| {
+            let field_name = &field.ident;
+            let name = field_name.as_ref().map(|name| name.unraw().to_string()).unwrap_or_default();
+            if field_name.as_ref().is_some_and(|name| redacted.contains(name)) {
+                // Printed even when `None`, so the output does not reveal whether it was set.
+                quote! { debug.field(#name, &format_args!("<redacted>")); }
+            } else if is_ignored || !container_options.compact_debug {
+                quote! { debug.field(#name, &self.#field_name); }
+            } else {
+                quote! {
+                    if let Some(value) = &self.#field_name {
+                        debug.field(#name, value);
+                    }
+                }
+            }
+        });
+        let mut debug_generics = generics.clone();
+        for param in generics.type_params() {
+            let ident = &param.ident;
+            debug_generics.make_where_clause().predicates.push(syn::parse_quote! { #ident: core::fmt::Debug });
+        }
+        let debug_where_clause = &debug_generics.where_clause;
+        let name = optional_struct_name.to_string();
+        (
+            quote! {},
+            quote! {
+                impl #impl_generics core::fmt::Debug for #optional_ty #debug_where_clause {
+                    /// Prints the fields that are set (every field without `compact_debug`),
+                    /// masking the ones listed in `impl_debug_redact`.
+                    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        let mut debug = f.debug_struct(#name);
+                        #( #debug_fields )*
+                        debug.finish()
+                    }
+                }
+            },
+        )
+    } else {
+        (quote! { Debug, }, quote! {})
+    };
+
+    let sub = if container_options.generates("sub") {
+        quote! {
+            impl #impl_generics core::ops::Sub for #optional_ty #where_clause {
+                type Output = Self;
+
+                /// Clears every field that is set on `rhs`, keeping the rest of `self`.
+                fn sub(self, rhs: Self) -> Self {
+                    Self {
+                        #( #sub_fields, )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let diesel_changeset = if container_options.diesel_changeset {
+        let table_name = container_options.table.as_ref().map(|table| quote! { #[diesel(table_name = #table)] });
+        quote! {
+            #[derive(diesel::AsChangeset)]
+            #table_name
+        }
+    } else {
+        quote! {}
+    };
+
+    let (wasm_bindgen, wasm_accessors) = if container_options.wasm_bindgen {
+        if !generics.params.is_empty() {
+            return syn::Error::new_spanned(generics, "`wasm_bindgen` does not support generic structs")
+                .to_compile_error()
+                .into();
+        }
+        let accessors = fields
+            .iter()
+            .filter(|(field, _, _, _)| !is_wasm_unsafe(&field.ty))
+            .map(|&(ref field, is_ignored, is_optional, _)| {
+                let field_name = &field.ident;
+                let Some(name) = field_name.as_ref().map(|name| name.unraw()) else {
+                    return quote! {};
+                };
+                let setter = syn::Ident::new(&format!("set_{}", name), name.span());
+                let field_type = &field.ty;
+                let optional_type = if is_ignored || is_optional {
+                    quote! { #field_type }
+                } else {
+                    quote! { Option<#field_type> }
+                };
+                quote! {
+                    #[wasm_bindgen(getter)]
+                    pub fn #field_name(&self) -> #optional_type {
+                        self.#field_name.clone()
+                    }
+
+                    #[wasm_bindgen(setter)]
+                    pub fn #setter(&mut self, value: #optional_type) {
+                        self.#field_name = value;
+                    }
+                }
+            });
+        (
+            quote! { #[wasm_bindgen::prelude::wasm_bindgen] },
+            quote! {
+                #[wasm_bindgen::prelude::wasm_bindgen]
+                #allow_dead_code
+                impl #optional_struct_name {
+                    #( #accessors )*
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // Hand-rolled so that formats without field names, which hand structs over as a
+    // sequence, work alongside maps; in a map a repeated key keeps its last value.
+    let (derive_deserialize, custom_deserializer) = if container_options.custom_deserializer {
+        if !generics.params.is_empty() {
+            return syn::Error::new_spanned(generics, "`custom_deserializer` does not support generic structs")
+                .to_compile_error()
+                .into();
+        }
+        let expecting = format!("struct {}", optional_struct_name);
+        let type_name = optional_struct_name.to_string();
+        let bindings: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .map(|(index, _)| syn::Ident::new(&format!("__field{}", index), struct_name.span()))
+            .collect();
+        let field_names: Vec<_> = fields.iter().map(|(field, _, _, _)| &field.ident).collect();
+        let keys: Vec<_> = fields
+            .iter()
+            .map(|(field, _, _, options)| {
+                serialized_name(field, options, &container_options)
+                    .or_else(|| field.ident.as_ref().map(|name| name.unraw().to_string()))
+                    .unwrap_or_default()
+            })
+            .collect();
+        let value_types: Vec<_> = fields
+            .iter()
+            .map(|&(ref field, is_ignored, is_optional, _)| {
+                let field_type = &field.ty;
+                if !is_ignored && !is_optional {
+                    quote! { Option<#field_type> }
+                } else {
+                    quote! { #field_type }
+                }
+            })
+            .collect();
+        let seq_fields = bindings.iter().zip(&value_types).enumerate().map(|(index, (binding, value_type))| {
+            quote! {
+                let #binding = seq
+                    .next_element::<#value_type>()?
+                    .ok_or_else(|| ::optionalize::__private::serde::de::Error::invalid_length(#index, &self))?;
+            }
+        });
+        // Absent keys leave `Option` fields `None`; ignored non-`Option` fields must be present.
+        let map_fields = fields.iter().zip(&bindings).zip(&keys).map(|((&(_, is_ignored, is_optional, _), binding), key)| {
+            if is_ignored && !is_optional {
+                quote! { #binding.ok_or_else(|| ::optionalize::__private::serde::de::Error::missing_field(#key))? }
+            } else {
+                quote! { #binding.unwrap_or_default() }
+            }
+        });
+        let deserializer = quote! {
+            impl<'de> ::optionalize::__private::serde::Deserialize<'de> for #optional_struct_name {
+                fn deserialize<D: ::optionalize::__private::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct Visitor;
+
+                    impl<'de> ::optionalize::__private::serde::de::Visitor<'de> for Visitor {
+                        type Value = #optional_struct_name;
+
+                        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                            formatter.write_str(#expecting)
+                        }
+
+                        fn visit_seq<A: ::optionalize::__private::serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                            #( #seq_fields )*
+                            Ok(#optional_struct_name {
+                                #( #field_names: #bindings, )*
+                            })
+                        }
+
+                        fn visit_map<A: ::optionalize::__private::serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                            #( let mut #bindings: Option<#value_types> = None; )*
+                            while let Some(key) = map.next_key::<String>()? {
+                                match key.as_str() {
+                                    #( #keys => #bindings = Some(map.next_value()?), )*
+                                    _ => {
+                                        map.next_value::<::optionalize::__private::serde::de::IgnoredAny>()?;
+                                    }
+                                }
+                            }
+                            Ok(#optional_struct_name {
+                                #( #field_names: #map_fields, )*
+                            })
+                        }
+                    }
+
+                    deserializer.deserialize_struct(#type_name, &[#( #keys ),*], Visitor)
+                }
+            }
+        };
+        (quote! {}, deserializer)
+    } else {
+        (quote! { ::optionalize::__private::serde::Deserialize, }, quote! {})
+    };
+    // serde_derive's output refers to a `serde` crate, which the caller may not depend on.
+    let serde_crate = if container_options.custom_deserializer {
+        quote! {}
+    } else {
+        quote! { #[serde(crate = "::optionalize::__private::serde")] }
+    };
+
+    // Written like `/// line`, which becomes `#[doc = " line"]`.
+    let doc = container_options.doc.iter().map(|line| {
+        let line = format!(" {}", line.value());
+        quote! { #[doc = #line] }
+    });
+
+    // Generate the output tokens
+    let items = quote! {
+
+        #( #doc )*
+        #[derive(#derive_debug #derive_deserialize Clone)]
+        #serde_crate
+        #derive_copy
+        #derive_cmp
+        #non_exhaustive
+        #serde_bound
+        #serde_tag
+        #serde_content
+        #serde_untagged
+        #json_schema
+        #graphql
+        #typescript
+        #diesel_changeset
+        #wasm_bindgen
+        pub struct #optional_struct_name #generics #where_clause {
+            #( #optional_fields, )*
+        }
+
+        // Exhaustively destructuring both structs fails to compile if the listed fields do
+        // not match the original struct (which `optionalize!` cannot see), or if the
+        // Optional lost or gained a field other than the excluded ones.
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_field_parity #impl_generics (original: #struct_ty, optional: #optional_ty) #where_clause {
+                let #struct_path { #( #source_fields: _, )* } = original;
+                let #optional_struct_name { #( #parity_fields: _, )* } = optional;
+            }
+        };
+
+        impl #impl_generics AsRef<#optional_ty> for #optional_ty #where_clause {
+            fn as_ref(&self) -> &Self {
+                self
+            }
+        }
+
+        #to_active
+        #to_create_active
+        #deny_extra_fields
+        #( #ignored_option_warnings )*
+        #( #legacy_ignore_warnings )*
+        #soft_delete
+        #soft_undelete
+
+        #into_active_model
+
+        #patch
+
+        #constrained_patch
+
+        #patch_ref
+
+        #shrink
+
+        #from_model
+        #snapshot
+
+        #find_by
+        #paginate
+        #find_or_create
+        #batch_update
+        #returning
+        #check_version
+        #save
+
+        #update_from
+
+        #from
+
+        #from_hashmap
+        #from_env
+
+        #to_hashmap
+        #generic_optional
+        #try_from_json
+        #into_json
+
+        #try_complete
+
+        #merge
+
+        #retain
+        #constants
+
+        #sub
+
+        #builder
+
+        #typestate
+        #required_parts
+        #newtype
+
+        #default
+
+        #transpose
+
+        #compact_debug
+        #custom_deserializer
+
+        #partial_eq
+
+        #column_types
+
+        #wasm_accessors
+    };
+
+    // `OptionalizeTrait::Optional` must be `Default + Clone`; spelling that out as a
+    // where clause lets generic structs whose parameters are not `Clone` still derive.
+    // Without type or const parameters the bound would be on a concrete type, which is an
+    // error of its own when the Optional is not `Default`.
+    let trait_impl = if foreign {
+        quote! {}
+    } else {
+        let optional_path = match &container_options.module {
+            Some(module) => quote! { #module::#optional_ty },
+            None => optional_ty.clone(),
+        };
+        let mut trait_generics = generics.clone();
+        if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+            trait_generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote! { #optional_path: Default + Clone });
+        }
+        let trait_where_clause = &trait_generics.where_clause;
+        quote! {
+            impl #impl_generics ::optionalize::OptionalizeTrait for #struct_ty #trait_where_clause {
+                type Optional = #optional_path;
+            }
+        }
+    };
+
+    // With `module = "..."` everything but the `OptionalizeTrait` impl moves into a
+    // child module that sees the source struct's scope through `use super::*`.
+    let expanded = if let Some(module) = &container_options.module {
+        quote! {
+            #trait_impl
+
+            pub mod #module {
+                use super::*;
+
+                #items
+            }
+        }
+    } else {
+        quote! {
+            #trait_impl
+
+            #items
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Name of the `ActiveModel` field that `field` maps to: its `sea_orm_column_name`, if any.
+fn active_model_field<'a>(field: &'a syn::Field, options: &'a FieldOptions) -> Option<&'a syn::Ident> {
+    options.column_name.as_ref().or(field.ident.as_ref())
+}
+
+/// The serialized name of a field if it differs from the Rust name.
+///
+/// An explicit `rename` wins, then the field's `rename_all`, then the struct's `rename_all`.
+fn serialized_name(field: &syn::Field, options: &FieldOptions, container_options: &ContainerOptions) -> Option<String> {
+    if let Some(rename) = &options.rename {
+        return Some(rename.value());
+    }
+    let rule = options.rename_all.or(container_options.rename_all)?;
+    let name = field.ident.as_ref()?.unraw().to_string();
+    Some(rule.apply_to_field(&name))
+}
+
+/// Collection types wasm-bindgen cannot pass across the JS boundary; `wasm_bindgen`
+/// generates no accessors for fields of these types.
+fn is_wasm_unsafe(ty: &Type) -> bool {
+    let ty = option_inner_type(ty).unwrap_or(ty);
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path.path.segments.last().is_some_and(|segment| {
+        ["HashMap", "HashSet", "BTreeMap", "BTreeSet", "VecDeque"].contains(&segment.ident.to_string().as_str())
+    })
+}
+
+/// The struct's generic parameters as arguments, e.g. `'a, T, N` for `<'a, T: Clone, const N: usize>`.
+fn generic_args(generics: &syn::Generics) -> Vec<syn::GenericArgument> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Lifetime(lifetime) => syn::GenericArgument::Lifetime(lifetime.lifetime.clone()),
+            syn::GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                syn::parse_quote! { #ident }
+            }
+            syn::GenericParam::Const(constant) => {
+                let ident = &constant.ident;
+                syn::GenericArgument::Const(syn::parse_quote! { #ident })
+            }
+        })
+        .collect()
+}
+
+/// Whether `ty` is one of the `std::num::NonZero*` types, which SeaORM has no column
+/// value for; generated SeaORM code converts them with `get()`. Only the std names match,
+/// bare or under `std::num`/`core::num`, so a user type such as `NonZeroAmount` does not.
+fn is_non_zero(ty: &Type) -> bool {
+    const NON_ZERO: &[&str] = &[
+        "NonZeroU8", "NonZeroU16", "NonZeroU32", "NonZeroU64", "NonZeroU128", "NonZeroUsize",
+        "NonZeroI8", "NonZeroI16", "NonZeroI32", "NonZeroI64", "NonZeroI128", "NonZeroIsize",
+    ];
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    if type_path.qself.is_some() {
+        return false;
+    }
+    let segments: Vec<_> = type_path.path.segments.iter().collect();
+    let Some((last, prefix)) = segments.split_last() else {
+        return false;
+    };
+    let prefix: Vec<_> = prefix.iter().map(|segment| segment.ident.to_string()).collect();
+    let in_num = prefix.is_empty() || prefix == ["std", "num"] || prefix == ["core", "num"];
+    let is_std_name = match &last.arguments {
+        syn::PathArguments::None => NON_ZERO.contains(&last.ident.to_string().as_str()),
+        syn::PathArguments::AngleBracketed(_) => last.ident == "NonZero",
+        syn::PathArguments::Parenthesized(_) => false,
+    };
+    in_num && is_std_name
+}
+
+/// Whether `ty` is chrono's `NaiveDateTime`, also under SeaORM's alias `DateTime`; the
+/// alias takes no arguments, unlike chrono's own `DateTime<Tz>`.
+fn is_naive_date_time(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path.path.segments.last().is_some_and(|segment| {
+        (segment.ident == "NaiveDateTime" || segment.ident == "DateTime") && segment.arguments.is_none()
+    })
+}
+
+/// Whether `ty` names one of the type or const parameters in `generics`.
+fn mentions_generic_param(ty: &Type, generics: &syn::Generics) -> bool {
+    let tokens = quote! { #ty }.to_string();
+    let words: Vec<_> = tokens.split(|c: char| !(c.is_alphanumeric() || c == '_')).collect();
+    generics
+        .type_params()
+        .map(|param| &param.ident)
+        .chain(generics.const_params().map(|param| &param.ident))
+        .any(|ident| words.contains(&ident.to_string().as_str()))
+}
+
+/// Returns `T` for a type spelled `Option<T>` (or any path ending in `Option<T>`).
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
 }
@@ -1,2 +1,68 @@
-pub use optionalize_macro::Optionalize;
-pub use optionalize_core::OptionalizeTrait;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Generated code names everything through `::optionalize`, including in this crate's tests.
+extern crate self as optionalize;
+
+pub use optionalize_macro::{optionalize, Optionalize};
+pub use optionalize_core::{
+    FieldSet, FieldState, FieldUnset, MissingFieldError, OptimisticLockError, OptionalizeMerge, OptionalizeTrait,
+};
+
+#[cfg(feature = "std")]
+pub use optionalize_core::{GenericOptional, MissingFieldsError};
+
+/// The derive together with the traits and error types it implements and returns:
+/// `use optionalize::prelude::*;`.
+pub mod prelude {
+    pub use optionalize_core::prelude::*;
+    pub use optionalize_macro::{optionalize, Optionalize};
+}
+
+/// Not public API: what generated code refers to through `::optionalize::__private`.
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+
+    #[cfg(feature = "json")]
+    pub use serde_json;
+
+    #[cfg(all(feature = "sea-orm", not(test)))]
+    pub use {chrono, sea_orm};
+
+    // This crate's own tests expand SeaORM code against stand-ins for both crates.
+    #[cfg(test)]
+    pub use crate::test::{chrono, sea_orm};
+}
+
+#[cfg(feature = "json-schema")]
+pub use schemars;
+
+#[cfg(feature = "ts")]
+pub use ts_rs;
+
+#[cfg(feature = "diesel")]
+pub use diesel;
+
+#[cfg(feature = "wasm")]
+pub use wasm_bindgen;
+
+#[cfg(feature = "graphql")]
+pub use async_graphql;
+
+#[cfg(feature = "serde")]
+pub use serde;
+
+#[cfg(feature = "json")]
+pub use serde_json;
+
+#[cfg(feature = "sea-orm")]
+pub use sea_orm;
+
+#[cfg(feature = "sqlx")]
+pub use sqlx;
+
+#[cfg(feature = "axum")]
+pub use axum;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,2237 @@
+use crate::{MissingFieldError, Optionalize, OptionalizeMerge, OptionalizeTrait};
+
+/// Minimal stand-in for the parts of `sea_orm` the generated code refers to.
+#[allow(dead_code)]
+pub mod sea_orm {
+    #[derive(Debug, PartialEq)]
+    pub enum ActiveValue<V> {
+        Set(V),
+        Unchanged(V),
+        NotSet,
+    }
+
+    pub trait IntoActiveModel<A> {
+        fn into_active_model(self) -> A;
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum ColumnType {
+        Json,
+        Text,
+    }
+
+    pub mod prelude {}
+}
+
+pub mod chrono {
+    pub type NaiveDateTime = u64;
+
+    pub struct Utc;
+
+    pub struct DateTime(u64);
+
+    impl Utc {
+        pub fn now() -> DateTime {
+            DateTime(1_700_000_000)
+        }
+    }
+
+    impl DateTime {
+        pub fn naive_utc(&self) -> u64 {
+            self.0
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct ActiveModel {
+    pub id: sea_orm::ActiveValue<i32>,
+    pub name: sea_orm::ActiveValue<String>,
+    pub email: sea_orm::ActiveValue<Option<String>>,
+}
+
+#[allow(dead_code)]
+#[derive(Optionalize, Debug, Clone, PartialEq)]
+#[optionalize(constrained_patch)]
+pub struct User {
+    #[optionalize(skip)]
+    pub id: i32,
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[test]
+fn test_default_optional() {
+    let empty = <User as OptionalizeTrait>::Optional::default();
+    assert_eq!(empty.id, 0);
+    assert_eq!(empty.name, None);
+    assert_eq!(empty.email, None);
+}
+
+#[test]
+fn test_as_optional_and_as_ref() {
+    fn name_of(optional: impl AsRef<UserOptional>) -> Option<String> {
+        optional.as_ref().name.clone()
+    }
+
+    let user = User {
+        id: 1,
+        name: "user123".to_string(),
+        email: None,
+    };
+    let optional = user.as_optional();
+    assert_eq!(optional.id, 1);
+    assert_eq!(name_of(&optional), Some("user123".to_string()));
+    assert_eq!(name_of(optional), Some("user123".to_string()));
+}
+
+#[test]
+fn test_merge_prefers_self() {
+    let patch = UserOptional {
+        id: 1,
+        name: Some("patched".to_string()),
+        email: None,
+    };
+    let defaults = UserOptional {
+        id: 2,
+        name: Some("default".to_string()),
+        email: Some("default@example.com".to_string()),
+    };
+
+    let merged = patch.merge(defaults);
+    assert_eq!(merged.id, 1);
+    assert_eq!(merged.name, Some("patched".to_string()));
+    assert_eq!(merged.email, Some("default@example.com".to_string()));
+}
+
+#[test]
+fn test_merge_trait_in_generic_code() {
+    fn apply_defaults<O: OptionalizeMerge>(user_patch: O, defaults: O) -> O {
+        user_patch.merge(defaults)
+    }
+
+    let patch: <User as OptionalizeTrait>::Optional = UserOptional {
+        id: 1,
+        name: None,
+        email: None,
+    };
+    let defaults = UserOptional {
+        id: 1,
+        name: Some("default".to_string()),
+        email: None,
+    };
+
+    let merged = apply_defaults(patch, defaults);
+    assert_eq!(merged.name, Some("default".to_string()));
+    assert_eq!(merged.email, None);
+}
+
+#[test]
+fn test_sub_removes_fields_set_on_rhs() {
+    let pending = UserOptional {
+        id: 1,
+        name: Some("new name".to_string()),
+        email: Some("new@example.com".to_string()),
+    };
+    let saved = UserOptional {
+        id: 2,
+        name: Some("new name".to_string()),
+        email: None,
+    };
+
+    let remaining = pending - saved;
+    assert_eq!(remaining.id, 1);
+    assert_eq!(remaining.name, None);
+    assert_eq!(remaining.email, Some("new@example.com".to_string()));
+}
+
+#[test]
+fn test_patch_and_add() {
+    let user = User {
+        id: 1,
+        name: "user123".to_string(),
+        email: Some("user@example.com".to_string()),
+    };
+    let patch = UserOptional {
+        id: 99,
+        name: Some("new_user123".to_string()),
+        email: None,
+    };
+
+    let expected = User {
+        id: 1,
+        name: "new_user123".to_string(),
+        email: Some("user@example.com".to_string()),
+    };
+    assert_eq!(patch.clone().patch(user.clone()), expected);
+    assert_eq!(user + patch, expected);
+}
+
+#[test]
+fn test_from_struct_wraps_fields() {
+    let user = User {
+        id: 1,
+        name: "user123".to_string(),
+        email: None,
+    };
+
+    let optional = UserOptional::from_struct(user.clone());
+    assert_eq!(optional.id, 1);
+    assert_eq!(optional.name, Some("user123".to_string()));
+    assert_eq!(optional.email, None);
+    assert_eq!(UserOptional::from(user).name, optional.name);
+}
+
+#[test]
+fn test_retain_fields() {
+    let optional = UserOptional {
+        id: 1,
+        name: Some("user123".to_string()),
+        email: Some("user@example.com".to_string()),
+    };
+
+    let retained = optional.retain_fields(&["email"]);
+    assert_eq!(retained.id, 1);
+    assert_eq!(retained.name, None);
+    assert_eq!(retained.email, Some("user@example.com".to_string()));
+}
+
+#[test]
+fn test_try_complete() {
+    let complete = UserOptional {
+        id: 1,
+        name: Some("user123".to_string()),
+        email: None,
+    };
+    assert!(complete.is_complete());
+    assert_eq!(
+        complete.try_complete(),
+        Ok(User {
+            id: 1,
+            name: "user123".to_string(),
+            email: None,
+        })
+    );
+
+    let incomplete = UserOptional {
+        id: 1,
+        name: None,
+        email: Some("user@example.com".to_string()),
+    };
+    assert!(!incomplete.is_complete());
+    assert_eq!(User::try_from(incomplete), Err(MissingFieldError { field: "name" }));
+}
+
+#[test]
+fn test_constrained_patch() {
+    let base = User { id: 1, name: "user123".to_string(), email: None };
+    let reject_empty = |field: &str, value: &dyn std::fmt::Debug| {
+        if format!("{:?}", value) == "\"\"" {
+            Err(format!("{} must not be empty", field))
+        } else {
+            Ok(())
+        }
+    };
+
+    let patch = UserOptional { id: 1, name: Some(String::new()), email: None };
+    assert_eq!(patch.constrained_patch(base.clone(), reject_empty), Err("name must not be empty".to_string()));
+
+    let patch = UserOptional { id: 1, name: None, email: Some("user@example.com".to_string()) };
+    let patched = patch.constrained_patch(base, reject_empty).unwrap();
+    assert_eq!(patched.email, Some("user@example.com".to_string()));
+}
+
+#[test]
+fn test_into_option_of_original() {
+    let complete: Option<User> = UserOptional { id: 1, name: Some("user123".to_string()), email: None }.into();
+    assert_eq!(complete.map(|user| user.name), Some("user123".to_string()));
+    let incomplete: Option<User> = UserOptional { id: 1, name: None, email: None }.into();
+    assert_eq!(incomplete, None);
+}
+
+mod builder {
+    use super::sea_orm;
+    use crate::{MissingFieldError, Optionalize};
+
+    #[allow(dead_code)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub name: sea_orm::ActiveValue<String>,
+        pub bio: sea_orm::ActiveValue<Option<String>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    #[optionalize(builder)]
+    pub struct Profile {
+        #[optionalize(skip)]
+        pub id: i32,
+        #[optionalize(into)]
+        pub name: String,
+        pub bio: Option<String>,
+    }
+
+    #[test]
+    fn test_builder_build() {
+        let optional = ProfileOptionalBuilder::new(1).name("alice").build();
+        assert_eq!(optional.id, 1);
+        assert_eq!(optional.name, Some("alice".to_string()));
+        assert_eq!(optional.bio, None);
+    }
+
+    #[test]
+    fn test_builder_build_checked() {
+        let profile = ProfileOptionalBuilder::new(1)
+            .name(String::from("alice"))
+            .bio("hello".to_string())
+            .build_checked();
+        assert_eq!(
+            profile,
+            Ok(Profile {
+                id: 1,
+                name: "alice".to_string(),
+                bio: Some("hello".to_string()),
+            })
+        );
+
+        let missing = ProfileOptionalBuilder::new(1).build_checked();
+        assert_eq!(missing, Err(MissingFieldError { field: "name" }));
+    }
+}
+
+mod rename {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    pub struct ActiveModel {
+        pub display_name: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[cfg_attr(feature = "json-schema", optionalize(json_schema))]
+    #[cfg_attr(feature = "ts", optionalize(typescript))]
+    pub struct Account {
+        #[optionalize(rename = "displayName")]
+        pub display_name: String,
+    }
+
+    #[test]
+    fn test_rename_is_used_for_deserialization() {
+        let optional: AccountOptional = serde_json::from_str(r#"{"displayName": "alice"}"#).unwrap();
+        assert_eq!(optional.display_name, Some("alice".to_string()));
+    }
+
+    #[cfg(feature = "json-schema")]
+    #[test]
+    fn test_json_schema_uses_renamed_field() {
+        let schema = serde_json::to_value(schemars::schema_for!(AccountOptional)).unwrap();
+        assert!(schema["properties"].get("displayName").is_some());
+    }
+
+    #[cfg(feature = "ts")]
+    #[test]
+    fn test_typescript_fields_are_optional() {
+        use ts_rs::TS;
+        assert!(AccountOptional::decl().contains("displayName?: string"));
+    }
+}
+
+mod rename_all {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    pub struct ActiveModel {
+        pub first_name: sea_orm::ActiveValue<String>,
+        pub last_name: sea_orm::ActiveValue<String>,
+        pub legacy_id: sea_orm::ActiveValue<i64>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(rename_all = "camelCase")]
+    pub struct Person {
+        pub first_name: String,
+        #[optionalize(rename = "surname")]
+        pub last_name: String,
+        #[optionalize(rename_all = "none")]
+        pub legacy_id: i64,
+    }
+
+    #[test]
+    fn test_field_rename_all_overrides_struct_rename_all() {
+        let optional: PersonOptional =
+            serde_json::from_str(r#"{"firstName": "Ada", "surname": "Lovelace", "legacy_id": 7}"#).unwrap();
+        assert_eq!(optional.first_name, Some("Ada".to_string()));
+        assert_eq!(optional.last_name, Some("Lovelace".to_string()));
+        assert_eq!(optional.legacy_id, Some(7));
+    }
+}
+
+mod compact {
+    use crate::{Optionalize, OptionalizeTrait};
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, merge)]
+    pub struct Sensor {
+        pub reading: u16,
+    }
+
+    #[test]
+    fn test_compact_keeps_opted_in_methods() {
+        let empty: <Sensor as OptionalizeTrait>::Optional = SensorOptional { reading: None };
+        let merged = empty.merge(SensorOptional { reading: Some(3) });
+        assert_eq!(merged.reading, Some(3));
+    }
+}
+
+mod visibility {
+    pub mod inner {
+        use crate::Optionalize;
+
+        #[allow(dead_code)]
+        #[derive(Optionalize)]
+        #[optionalize(compact)]
+        pub struct Session {
+            pub(crate) token: String,
+            secret: String,
+        }
+
+        pub fn empty() -> SessionOptional {
+            SessionOptional { token: None, secret: None }
+        }
+
+        #[allow(dead_code)]
+        #[derive(Optionalize)]
+        #[optionalize(compact, pub_fields)]
+        pub struct Credentials {
+            password: String,
+        }
+    }
+
+    #[test]
+    fn test_pub_fields_overrides_visibility() {
+        let optional = inner::CredentialsOptional { password: Some("hunter2".to_string()) };
+        assert_eq!(optional.password, Some("hunter2".to_string()));
+    }
+
+    #[test]
+    fn test_field_visibility_is_forwarded() {
+        let optional = inner::empty();
+        assert_eq!(optional.token, None);
+    }
+}
+
+mod flatten_option {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub notes: sea_orm::ActiveValue<Option<String>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    pub struct Ticket {
+        #[optionalize(flatten_option)]
+        pub notes: Option<Option<String>>,
+    }
+
+    #[test]
+    fn test_flatten_option_wraps_once() {
+        let optional = TicketOptional { notes: Some("note".to_string()) };
+        assert_eq!(
+            optional.clone().to_active().notes,
+            sea_orm::ActiveValue::Set(Some("note".to_string()))
+        );
+        assert_eq!(
+            optional.patch(Ticket { notes: None }),
+            Ticket { notes: Some(Some("note".to_string())) }
+        );
+
+        let empty = TicketOptional { notes: None };
+        assert_eq!(empty.to_active().notes, sea_orm::ActiveValue::NotSet);
+    }
+}
+
+mod primary_key {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub title: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_into_active_model)]
+    pub struct Post {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub title: String,
+    }
+
+    #[test]
+    fn test_primary_key_is_unchanged() {
+        let active = PostOptional { id: 5, title: None }.to_active();
+        assert_eq!(active.id, sea_orm::ActiveValue::Unchanged(5));
+        assert_eq!(active.title, sea_orm::ActiveValue::NotSet);
+    }
+
+    #[test]
+    fn test_into_active_model() {
+        use sea_orm::IntoActiveModel;
+
+        fn save(model: impl IntoActiveModel<ActiveModel>) -> ActiveModel {
+            model.into_active_model()
+        }
+
+        let active = save(PostOptional { id: 5, title: Some("hello".to_string()) });
+        assert_eq!(active.id, sea_orm::ActiveValue::Unchanged(5));
+        assert_eq!(active.title, sea_orm::ActiveValue::Set("hello".to_string()));
+    }
+}
+
+mod sea_orm_skip {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    #[optionalize(sea_orm_skip)]
+    pub struct Settings {
+        pub theme: String,
+    }
+
+    #[test]
+    fn test_sea_orm_skip_keeps_other_methods() {
+        let settings = Settings { theme: "light".to_string() };
+        let patch = SettingsOptional { theme: Some("dark".to_string()) };
+        assert_eq!(patch.patch(settings), Settings { theme: "dark".to_string() });
+    }
+}
+
+mod from_env {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, from_env, prefix = "OPTIONALIZE_TEST_")]
+    pub struct Config {
+        pub port: u16,
+        pub host: String,
+        pub workers: u8,
+        #[optionalize(skip)]
+        pub debug: bool,
+    }
+
+    #[test]
+    fn test_from_env_parses_set_variables() {
+        std::env::set_var("OPTIONALIZE_TEST_PORT", "8080");
+        std::env::set_var("OPTIONALIZE_TEST_WORKERS", "many");
+        std::env::remove_var("OPTIONALIZE_TEST_HOST");
+
+        let config = ConfigOptional::from_env();
+        assert_eq!(config.port, Some(8080));
+        assert_eq!(config.host, None);
+        assert_eq!(config.workers, None);
+        assert!(!config.debug);
+    }
+}
+
+mod from_hashmap {
+    use crate::Optionalize;
+    use std::collections::HashMap;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, impl_from_hashmap, to_hashmap)]
+    pub struct SignupForm {
+        #[optionalize(rename = "userName")]
+        pub user_name: String,
+        pub age: u8,
+        pub newsletter: Option<bool>,
+    }
+
+    #[test]
+    fn test_from_hashmap_parses_known_fields() {
+        let form = HashMap::from([
+            ("userName".to_string(), "alice".to_string()),
+            ("age".to_string(), "not a number".to_string()),
+            ("newsletter".to_string(), "true".to_string()),
+        ]);
+
+        let optional = SignupFormOptional::from(form);
+        assert_eq!(optional.user_name, Some("alice".to_string()));
+        assert_eq!(optional.age, None);
+        assert_eq!(optional.newsletter, Some(true));
+    }
+
+    #[test]
+    fn test_to_hashmap_skips_unset_fields() {
+        let optional = SignupFormOptional {
+            user_name: Some("alice".to_string()),
+            age: None,
+            newsletter: Some(false),
+        };
+
+        let map = optional.to_hashmap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["userName"], "alice");
+        assert_eq!(map["newsletter"], "false");
+    }
+}
+
+mod accumulate_errors {
+    use crate::{MissingFieldsError, Optionalize};
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    #[optionalize(sea_orm_skip, accumulate_errors)]
+    pub struct Address {
+        pub street: String,
+        pub city: String,
+        pub zip: String,
+        pub note: Option<String>,
+    }
+
+    #[test]
+    fn test_accumulate_errors_reports_every_missing_field() {
+        let optional = AddressOptional {
+            street: None,
+            city: Some("Paris".to_string()),
+            zip: None,
+            note: None,
+        };
+        assert_eq!(
+            optional.try_complete(),
+            Err(MissingFieldsError { fields: vec!["street", "zip"] })
+        );
+
+        let complete = AddressOptional {
+            street: Some("Main St".to_string()),
+            city: Some("Paris".to_string()),
+            zip: Some("75001".to_string()),
+            note: None,
+        };
+        assert_eq!(
+            Address::try_from(complete),
+            Ok(Address {
+                street: "Main St".to_string(),
+                city: "Paris".to_string(),
+                zip: "75001".to_string(),
+                note: None,
+            })
+        );
+    }
+}
+
+#[cfg(feature = "diesel")]
+mod diesel_changeset {
+    use crate::Optionalize;
+
+    diesel::table! {
+        users (id) {
+            id -> Integer,
+            name -> Text,
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, diesel_changeset, table = "users")]
+    pub struct User {
+        #[optionalize(skip)]
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[test]
+    fn test_diesel_changeset_derives() {
+        fn assert_changeset<T: diesel::AsChangeset>(_: T) {}
+        assert_changeset(UserOptional { id: 1, name: Some("alice".to_string()) });
+    }
+}
+
+mod generics {
+    use crate::{Optionalize, OptionalizeTrait};
+    use std::fmt::Debug;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, Clone, PartialEq)]
+    #[optionalize(sea_orm_skip, builder)]
+    pub struct Wrapper<T: Clone, L>
+    where
+        L: Debug,
+    {
+        pub value: T,
+        pub label: Option<L>,
+    }
+
+    #[test]
+    fn test_generic_struct_with_where_clause() {
+        let patch: <Wrapper<u8, String> as OptionalizeTrait>::Optional = WrapperOptional { value: Some(2), label: None };
+        let base = Wrapper { value: 1u8, label: Some("base".to_string()) };
+        assert_eq!(
+            base + patch.clone(),
+            Wrapper { value: 2, label: Some("base".to_string()) }
+        );
+        assert_eq!(patch.merge(WrapperOptional { value: Some(3), label: None }).value, Some(2));
+        assert_eq!(
+            WrapperOptionalBuilder::<u8, String>::new().value(4).build_checked(),
+            Ok(Wrapper { value: 4, label: None })
+        );
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip)]
+    pub struct Tagged<T: Clone> {
+        #[optionalize(skip)]
+        pub id: u32,
+        #[optionalize(skip)]
+        pub tag: T,
+        pub name: String,
+    }
+
+    #[test]
+    fn test_default_with_concrete_and_generic_ignored_fields() {
+        let empty = <Tagged<String> as OptionalizeTrait>::Optional::default();
+        assert_eq!((empty.id, empty.tag, empty.name), (0, String::new(), None));
+    }
+}
+
+mod module {
+    use super::sea_orm;
+    use crate::{Optionalize, OptionalizeTrait};
+
+    #[allow(dead_code)]
+    pub struct ActiveModel {
+        pub sku: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(module = "optional")]
+    pub struct Product {
+        pub sku: String,
+    }
+
+    #[test]
+    fn test_generated_items_live_in_module() {
+        let optional: <Product as OptionalizeTrait>::Optional = optional::ProductOptional { sku: None };
+        assert!(!optional.is_complete());
+        assert_eq!(optional.to_active().sku, sea_orm::ActiveValue::NotSet);
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, emit_as_mod = "generated")]
+    pub struct Variant {
+        pub color: String,
+    }
+
+    #[test]
+    fn test_emit_as_mod_is_an_alias() {
+        let optional: <Variant as OptionalizeTrait>::Optional = generated::VariantOptional { color: None };
+        assert!(!optional.is_complete());
+    }
+}
+
+mod foreign {
+    use crate::optionalize;
+
+    mod external {
+        #[derive(Debug, PartialEq)]
+        pub struct Point {
+            pub x: i32,
+            pub y: i32,
+        }
+    }
+
+    optionalize!(
+        #[optionalize(sea_orm_skip)]
+        external::Point {
+            pub x: i32,
+            pub y: i32,
+        }
+    );
+
+    #[test]
+    fn test_optionalize_macro_for_foreign_struct() {
+        let patch = PointOptional { x: None, y: Some(5) };
+        assert_eq!(external::Point { x: 1, y: 2 } + patch, external::Point { x: 1, y: 5 });
+        assert_eq!(
+            external::Point::try_from(PointOptional::from(external::Point { x: 3, y: 4 })),
+            Ok(external::Point { x: 3, y: 4 })
+        );
+    }
+}
+
+#[cfg(feature = "wasm")]
+mod wasm {
+    use crate::Optionalize;
+    use std::collections::HashMap;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, wasm_bindgen)]
+    pub struct Preferences {
+        pub volume: u8,
+        pub nickname: String,
+        pub shortcuts: HashMap<String, String>,
+    }
+
+    #[test]
+    fn test_wasm_accessors() {
+        let mut optional = PreferencesOptional { volume: None, nickname: None, shortcuts: None };
+        optional.set_volume(Some(7));
+        optional.set_nickname(Some("neo".to_string()));
+        assert_eq!(optional.volume(), Some(7));
+        assert_eq!(optional.nickname(), Some("neo".to_string()));
+    }
+}
+
+mod derive_copy {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, derive_copy)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[test]
+    fn test_optional_is_copy() {
+        let original = PointOptional { x: Some(1), y: None };
+        let copy = original;
+        assert_eq!(original.x, copy.x);
+        assert_eq!(original.y, copy.y);
+    }
+}
+
+mod non_exhaustive {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, from)]
+    #[non_exhaustive]
+    pub struct Config {
+        pub retries: u8,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, from, non_exhaustive)]
+    pub struct Limits {
+        pub max: u32,
+    }
+
+    #[test]
+    fn test_non_exhaustive_optional_still_converts() {
+        let config = ConfigOptional::from(Config { retries: 3 });
+        assert_eq!(config.retries, Some(3));
+        let limits = LimitsOptional::from(Limits { max: 10 });
+        assert_eq!(limits.max, Some(10));
+    }
+}
+
+mod trait_object {
+    use crate::Optionalize;
+    use serde::{Deserialize, Deserializer};
+    use std::fmt::Debug;
+
+    pub trait Shape: Debug {
+        fn area(&self) -> f64;
+        fn box_clone(&self) -> Box<dyn Shape>;
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    pub struct Square {
+        pub side: f64,
+    }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 {
+            self.side * self.side
+        }
+
+        fn box_clone(&self) -> Box<dyn Shape> {
+            Box::new(self.clone())
+        }
+    }
+
+    impl Clone for Box<dyn Shape> {
+        fn clone(&self) -> Self {
+            self.box_clone()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Box<dyn Shape> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Ok(Box::new(Square::deserialize(deserializer)?))
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact)]
+    pub struct Drawing {
+        pub title: String,
+        pub shape: Option<Box<dyn Shape>>,
+    }
+
+    #[test]
+    fn test_boxed_trait_object_is_not_double_wrapped() {
+        let drawing = DrawingOptional { title: None, shape: Some(Box::new(Square { side: 2.0 })) };
+        let shape: &Option<Box<dyn Shape>> = &drawing.shape;
+        assert_eq!(shape.as_ref().map(|shape| shape.area()), Some(4.0));
+    }
+}
+
+mod transpose {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, transpose)]
+    pub struct Profile {
+        pub name: String,
+        pub nickname: Option<Option<String>>,
+        pub avatar: Option<Option<String>>,
+    }
+
+    #[test]
+    fn test_transpose_collapses_nested_none() {
+        let optional = ProfileOptional { name: None, nickname: Some(None), avatar: Some(Some("a.png".to_string())) };
+        let single = optional.clone().transpose_nickname();
+        assert_eq!(single.nickname, None);
+        assert_eq!(single.avatar, Some(Some("a.png".to_string())));
+
+        let optional = ProfileOptional { avatar: Some(None), ..optional };
+        let all = optional.transpose_all();
+        assert_eq!(all.nickname, None);
+        assert_eq!(all.avatar, None);
+    }
+}
+
+mod allow_dead_code {
+    #![deny(dead_code)]
+
+    use crate::Optionalize;
+
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, builder, allow_dead_code)]
+    struct Internal {
+        #[optionalize(skip)]
+        id: i32,
+        label: String,
+    }
+
+    #[test]
+    fn test_unused_methods_do_not_warn() {
+        let optional = InternalOptional { id: 1, label: None };
+        assert_eq!(optional.id, 1);
+        assert!(optional.label.is_none());
+    }
+}
+
+mod sea_orm_column_type {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact)]
+    pub struct Document {
+        #[optionalize(sea_orm_column_type = "Json")]
+        pub body: String,
+        #[optionalize(sea_orm_column_type = "Text")]
+        pub summary: String,
+        pub title: String,
+    }
+
+    #[test]
+    fn test_column_types_lists_annotated_fields() {
+        assert_eq!(
+            DocumentOptional::column_types(),
+            vec![("body", sea_orm::ColumnType::Json), ("summary", sea_orm::ColumnType::Text)]
+        );
+    }
+}
+
+mod serde_bound {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, serde_bound = "T: serde::de::DeserializeOwned")]
+    pub struct Keyed<T: Clone + std::fmt::Debug> {
+        pub key: T,
+    }
+
+    #[test]
+    fn test_serde_bound_is_forwarded() {
+        let optional: KeyedOptional<u32> = serde_json::from_str(r#"{"key": 7}"#).unwrap();
+        assert_eq!(optional.key, Some(7));
+    }
+}
+
+mod compact_debug {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, compact_debug)]
+    pub struct Account<T> {
+        #[optionalize(skip)]
+        pub id: u32,
+        pub name: String,
+        pub email: Option<String>,
+        pub extra: T,
+    }
+
+    #[test]
+    fn test_compact_debug_omits_none_fields() {
+        let optional = AccountOptional::<u8> { id: 1, name: Some("Ada".to_string()), email: None, extra: None };
+        assert_eq!(format!("{:?}", optional), r#"AccountOptional { id: 1, name: "Ada" }"#);
+    }
+}
+
+mod serde_tag {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, serde_tag = "type")]
+    pub struct Invoice {
+        pub total: u32,
+    }
+
+    #[test]
+    fn test_serde_tag_input_deserializes() {
+        let optional: InvoiceOptional = serde_json::from_str(r#"{"type": "InvoiceOptional", "total": 5}"#).unwrap();
+        assert_eq!(optional.total, Some(5));
+    }
+}
+
+mod no_std {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    #[optionalize(sea_orm_skip, no_std)]
+    pub struct Reading {
+        pub celsius: i16,
+    }
+
+    #[test]
+    fn test_no_std_keeps_core_items() {
+        let patch = ReadingOptional { celsius: Some(21) };
+        assert_eq!(patch.clone().try_complete(), Ok(Reading { celsius: 21 }));
+        assert_eq!(Reading { celsius: 0 } + patch, Reading { celsius: 21 });
+    }
+}
+
+mod sea_orm_prelude {
+    use super::{sea_orm, ActiveModel};
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_prelude)]
+    pub struct User {
+        #[optionalize(skip)]
+        pub id: i32,
+        pub name: String,
+        pub email: Option<String>,
+    }
+
+    #[test]
+    fn test_to_active_through_prelude() {
+        let active = UserOptional { id: 3, name: Some("Ada".to_string()), email: None }.to_active();
+        assert_eq!(active.id, sea_orm::ActiveValue::Unchanged(3));
+        assert_eq!(active.name, sea_orm::ActiveValue::Set("Ada".to_string()));
+        assert_eq!(active.email, sea_orm::ActiveValue::NotSet);
+    }
+}
+
+mod typestate {
+    use crate::{FieldSet, FieldUnset, Optionalize};
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, typestate)]
+    pub struct Order<T: Clone + std::fmt::Debug> {
+        #[optionalize(skip)]
+        pub id: u64,
+        #[optionalize(required, into)]
+        pub customer: String,
+        #[optionalize(required)]
+        pub item: T,
+        pub note: Option<String>,
+    }
+
+    #[test]
+    fn test_typestate_build_requires_required_fields() {
+        let builder: OrderOptionalTypedBuilder<u8, FieldUnset, FieldUnset> = OrderOptionalTypedBuilder::new(9);
+        let builder: OrderOptionalTypedBuilder<u8, FieldUnset, FieldSet> = builder.note("fragile".to_string()).item(3);
+        let optional = builder.customer("ada").build();
+        assert_eq!(optional.id, 9);
+        assert_eq!(optional.customer, Some("ada".to_string()));
+        assert_eq!(optional.item, Some(3));
+        assert_eq!(optional.note, Some("fragile".to_string()));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, typestate)]
+    pub struct Parcel<T = String> {
+        #[optionalize(required)]
+        pub label: T,
+        pub weight: Option<u32>,
+    }
+
+    #[test]
+    fn test_typestate_with_defaulted_generic() {
+        let optional: ParcelOptional = ParcelOptionalTypedBuilder::new().label("fragile".to_string()).weight(4).build();
+        assert_eq!(optional.label, Some("fragile".to_string()));
+        assert_eq!(optional.weight, Some(4));
+    }
+}
+
+mod update_from {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, Clone, PartialEq)]
+    #[optionalize(sea_orm_skip, impl_update_from)]
+    pub struct Post {
+        #[optionalize(skip)]
+        pub id: u32,
+        pub title: String,
+        pub subtitle: Option<String>,
+    }
+
+    #[test]
+    fn test_update_from_patches_in_place() {
+        let mut post = Post { id: 1, title: "Draft".to_string(), subtitle: None };
+        post.update_from(PostOptional { id: 99, title: None, subtitle: Some("Intro".to_string()) });
+        assert_eq!(post, Post { id: 1, title: "Draft".to_string(), subtitle: Some("Intro".to_string()) });
+        post.update_from(PostOptional { id: 99, title: Some("Final".to_string()), subtitle: None });
+        assert_eq!(post.title, "Final");
+        assert_eq!(post.subtitle, Some("Intro".to_string()));
+    }
+}
+
+mod partial_eq_ignoring {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, partial_eq_ignoring = "updated_at, version")]
+    pub struct Article {
+        pub body: String,
+        pub updated_at: u64,
+        pub version: u32,
+    }
+
+    #[test]
+    fn test_partial_eq_skips_listed_fields() {
+        let current = ArticleOptional { body: Some("text".to_string()), updated_at: Some(1), version: Some(1) };
+        let incoming = ArticleOptional { body: Some("text".to_string()), updated_at: Some(2), version: None };
+        assert_eq!(current, incoming);
+        assert_ne!(current, ArticleOptional { body: None, ..incoming });
+    }
+}
+
+mod sea_orm_timestamp {
+    use super::{chrono, sea_orm};
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub title: sea_orm::ActiveValue<String>,
+        pub updated_at: sea_orm::ActiveValue<chrono::NaiveDateTime>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    #[optionalize(sea_orm_timestamp = "updated_at")]
+    pub struct Note {
+        pub title: String,
+        pub updated_at: chrono::NaiveDateTime,
+    }
+
+    #[test]
+    fn test_to_active_sets_timestamp() {
+        let active = NoteOptional { title: None }.to_active();
+        assert_eq!(active.title, sea_orm::ActiveValue::NotSet);
+        assert_eq!(active.updated_at, sea_orm::ActiveValue::Set(chrono::Utc::now().naive_utc()));
+    }
+
+    #[test]
+    fn test_timestamp_is_left_out_of_the_optional() {
+        let base = Note { title: "a".to_string(), updated_at: 1 };
+        let optional = NoteOptional::from(Note { title: "b".to_string(), updated_at: 2 });
+        assert_eq!(optional.title, Some("b".to_string()));
+        assert_eq!(base + optional, Note { title: "b".to_string(), updated_at: 1 });
+    }
+}
+
+mod sea_orm_not_set_default {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub created_at: sea_orm::ActiveValue<u64>,
+        pub name: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    pub struct Tag {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        #[optionalize(skip)]
+        #[optionalize(sea_orm_not_set_default)]
+        pub created_at: u64,
+        pub name: String,
+    }
+
+    #[test]
+    fn test_not_set_default_leaves_column_to_database() {
+        let active = TagOptional { id: 4, created_at: 0, name: Some("rust".to_string()) }.to_active();
+        assert_eq!(active.id, sea_orm::ActiveValue::Unchanged(4));
+        assert_eq!(active.created_at, sea_orm::ActiveValue::NotSet);
+        assert_eq!(active.name, sea_orm::ActiveValue::Set("rust".to_string()));
+    }
+}
+
+mod clone_on_none {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, Clone, PartialEq)]
+    #[optionalize(sea_orm_skip, clone_on_none)]
+    pub struct Page {
+        #[optionalize(skip)]
+        pub slug: String,
+        pub title: String,
+        pub summary: Option<String>,
+    }
+
+    #[test]
+    fn test_patch_ref_keeps_both_sides() {
+        let original = Page { slug: "home".to_string(), title: "Home".to_string(), summary: Some("Welcome".to_string()) };
+        let patch = PageOptional { slug: "ignored".to_string(), title: Some("Start".to_string()), summary: None };
+        let updated = patch.patch_ref(&original);
+        assert_eq!(updated, Page { slug: "home".to_string(), title: "Start".to_string(), summary: Some("Welcome".to_string()) });
+        assert_eq!(original.title, "Home");
+        assert_eq!(patch.title, Some("Start".to_string()));
+    }
+}
+
+mod sea_orm_model {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    pub mod entity {
+        pub struct Model {
+            pub id: i32,
+            pub name: String,
+            pub email: Option<String>,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub name: sea_orm::ActiveValue<String>,
+        pub email: sea_orm::ActiveValue<Option<String>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_model = "entity::Model")]
+    pub struct User {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub name: String,
+        pub email: Option<String>,
+    }
+
+    #[test]
+    fn test_from_model_sets_every_field() {
+        let optional = UserOptional::from_model(entity::Model { id: 2, name: "Ada".to_string(), email: None });
+        assert_eq!(optional.id, 2);
+        assert_eq!(optional.name, Some("Ada".to_string()));
+        assert_eq!(optional.email, None);
+    }
+}
+
+mod shrink {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, Clone, PartialEq)]
+    #[optionalize(sea_orm_skip, shrink)]
+    pub struct Settings {
+        #[optionalize(skip)]
+        pub id: u32,
+        pub theme: String,
+        pub language: String,
+        pub timezone: Option<String>,
+    }
+
+    #[test]
+    fn test_shrink_keeps_only_changes() {
+        let original = Settings { id: 1, theme: "dark".to_string(), language: "en".to_string(), timezone: None };
+        let mut edited = SettingsOptional::from(original.clone());
+        edited.language = Some("fr".to_string());
+        let changes = edited.shrink(&original);
+        assert_eq!(changes.id, 1);
+        assert_eq!(changes.theme, None);
+        assert_eq!(changes.language, Some("fr".to_string()));
+        assert_eq!(changes.timezone, None);
+    }
+}
+
+#[cfg(feature = "graphql")]
+mod graphql {
+    use crate::Optionalize;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, graphql, graphql_input_name = "UpdateUserInput")]
+    pub struct Member {
+        pub name: String,
+        #[optionalize(rename = "emailAddress")]
+        pub email: Option<String>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, input: MemberOptional) -> String {
+            input.name.unwrap_or_default()
+        }
+    }
+
+    #[test]
+    fn test_graphql_input_uses_configured_name() {
+        let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+        assert!(sdl.contains("input UpdateUserInput"));
+        assert!(sdl.contains("emailAddress: String"));
+        assert!(!sdl.contains("MemberOptional"));
+    }
+}
+
+mod non_zero {
+    use super::sea_orm;
+    use crate::Optionalize;
+    use std::num::{NonZeroI64, NonZeroU32};
+
+    // SeaORM has no column value for `NonZero*`, so the columns hold the plain integers
+    // and `to_active()` unwraps with `get()`.
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<u32>,
+        pub quantity: sea_orm::ActiveValue<u32>,
+        pub offset: sea_orm::ActiveValue<Option<i64>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    pub struct LineItem {
+        #[optionalize(skip)]
+        pub id: u32,
+        pub quantity: NonZeroU32,
+        pub offset: Option<NonZeroI64>,
+    }
+
+    #[test]
+    fn test_non_zero_fields_are_unwrapped_for_sea_orm() {
+        let quantity = NonZeroU32::new(3).unwrap();
+        let optional: LineItemOptional = serde_json::from_str(r#"{"id": 1, "quantity": 3}"#).unwrap();
+        assert_eq!(optional.quantity, Some(quantity));
+        assert_eq!(optional.offset, None);
+        assert!(serde_json::from_str::<LineItemOptional>(r#"{"id": 1, "quantity": 0}"#).is_err());
+
+        let active = optional.clone().to_active();
+        assert_eq!(active.id, sea_orm::ActiveValue::Unchanged(1));
+        assert_eq!(active.quantity, sea_orm::ActiveValue::Set(3));
+        assert_eq!(active.offset, sea_orm::ActiveValue::NotSet);
+        let offset = LineItemOptional { offset: NonZeroI64::new(-2), ..optional.clone() }.to_active().offset;
+        assert_eq!(offset, sea_orm::ActiveValue::Set(Some(-2)));
+        assert_eq!(optional.try_complete(), Ok(LineItem { id: 1, quantity, offset: None }));
+    }
+
+    /// Not one of the std types despite its name, so it is stored as it is.
+    #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct NonZeroAmount(u32);
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct PaymentActiveModel {
+        pub amount: sea_orm::ActiveValue<NonZeroAmount>,
+        pub installments: sea_orm::ActiveValue<u16>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_active_model_type = "PaymentActiveModel")]
+    pub struct Payment {
+        pub amount: NonZeroAmount,
+        pub installments: std::num::NonZero<u16>,
+    }
+
+    #[test]
+    fn test_only_std_non_zero_types_are_unwrapped() {
+        let optional = PaymentOptional { amount: Some(NonZeroAmount(5)), installments: std::num::NonZero::new(3) };
+        let active = optional.to_active();
+        assert_eq!(active.amount, sea_orm::ActiveValue::Set(NonZeroAmount(5)));
+        assert_eq!(active.installments, sea_orm::ActiveValue::Set(3));
+    }
+}
+
+#[cfg(feature = "json-schema")]
+mod schema_as {
+    use crate::Optionalize;
+    use serde::Deserialize;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, Deserialize)]
+    pub struct Uuid(u128);
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(compact, json_schema)]
+    pub struct Session {
+        #[optionalize(schema_as = "String")]
+        pub token: Uuid,
+    }
+
+    #[test]
+    fn test_schema_as_overrides_field_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(SessionOptional)).unwrap();
+        let token = &schema["properties"]["token"];
+        assert_eq!(token["type"], serde_json::json!(["string", "null"]));
+    }
+}
+
+mod sea_orm_active_value_set {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    fn to_hex(bytes: Vec<u8>) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub digest: sea_orm::ActiveValue<String>,
+        pub signature: sea_orm::ActiveValue<Option<String>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    pub struct Blob {
+        #[optionalize(sea_orm_active_value_set = "to_hex")]
+        pub digest: Vec<u8>,
+        #[optionalize(sea_orm_active_value_set = "to_hex")]
+        pub signature: Option<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_active_value_set_transforms_value() {
+        let active = BlobOptional { digest: Some(vec![0xab, 0x01]), signature: Some(vec![0xff]) }.to_active();
+        assert_eq!(active.digest, sea_orm::ActiveValue::Set("ab01".to_string()));
+        assert_eq!(active.signature, sea_orm::ActiveValue::Set(Some("ff".to_string())));
+        let active = BlobOptional { digest: None, signature: None }.to_active();
+        assert_eq!(active.digest, sea_orm::ActiveValue::NotSet);
+    }
+}
+
+mod forward_attrs {
+    use crate::Optionalize;
+    use serde::Deserialize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Deserialize)]
+    #[optionalize(sea_orm_skip, forward_attrs(serde))]
+    pub struct Contact {
+        #[serde(alias = "mail")]
+        pub email: String,
+        pub phone: String,
+    }
+
+    #[test]
+    fn test_forwarded_attributes_apply_to_optional() {
+        let optional: ContactOptional = serde_json::from_str(r#"{"mail": "a@b.c"}"#).unwrap();
+        assert_eq!(optional.email, Some("a@b.c".to_string()));
+        assert_eq!(optional.phone, None);
+    }
+}
+
+mod required_parts {
+    use crate::{MissingFieldError, Optionalize};
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, required_struct = "SignupRequiredParts")]
+    pub struct Signup {
+        #[optionalize(skip)]
+        pub id: u32,
+        #[optionalize(required)]
+        pub email: String,
+        #[optionalize(required)]
+        pub referrer: Option<String>,
+        pub nickname: String,
+        pub bio: Option<String>,
+    }
+
+    #[test]
+    fn test_into_required_parts() {
+        let optional = SignupOptional {
+            id: 1,
+            email: Some("a@b.c".to_string()),
+            referrer: Some("friend".to_string()),
+            nickname: None,
+            bio: Some("hi".to_string()),
+        };
+        let (required, optional) = optional.into_required_parts().unwrap();
+        assert_eq!(required.id, 1);
+        assert_eq!(required.email, "a@b.c");
+        assert_eq!(required.referrer, "friend");
+        assert_eq!(optional.nickname, None);
+        assert_eq!(optional.bio, Some("hi".to_string()));
+
+        let missing = SignupOptional { id: 1, email: Some("a@b.c".to_string()), referrer: None, nickname: None, bio: None };
+        assert_eq!(missing.into_required_parts().unwrap_err(), MissingFieldError { field: "referrer" });
+    }
+}
+
+mod debug_redact {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, constrained_patch, impl_debug_redact = "password, token")]
+    pub struct Credentials {
+        #[optionalize(skip)]
+        pub id: u32,
+        pub username: String,
+        pub password: String,
+        pub token: Option<String>,
+    }
+
+    #[test]
+    fn test_debug_redacts_listed_fields() {
+        let optional = CredentialsOptional {
+            id: 1,
+            username: Some("ada".to_string()),
+            password: Some("hunter2".to_string()),
+            token: None,
+        };
+        assert_eq!(
+            format!("{:?}", optional),
+            r#"CredentialsOptional { id: 1, username: Some("ada"), password: <redacted>, token: <redacted> }"#
+        );
+    }
+
+    #[test]
+    fn test_constrained_patch_skips_redacted_fields() {
+        let base = Credentials { id: 1, username: "ada".to_string(), password: "old".to_string(), token: None };
+        let patch = CredentialsOptional {
+            id: 1,
+            username: Some("grace".to_string()),
+            password: Some("hunter2".to_string()),
+            token: Some("secret".to_string()),
+        };
+        let seen = std::cell::RefCell::new(Vec::new());
+        let patched = patch
+            .constrained_patch(base, |field, value| {
+                seen.borrow_mut().push(format!("{}={:?}", field, value));
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+        assert_eq!(seen.into_inner(), vec![r#"username="grace""#.to_string()]);
+        assert_eq!(patched.password, "hunter2");
+    }
+}
+
+mod impl_hash {
+    use crate::Optionalize;
+    use std::collections::HashSet;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, impl_hash)]
+    pub struct Tag {
+        #[optionalize(skip)]
+        pub id: u32,
+        pub name: String,
+        pub color: Option<String>,
+    }
+
+    #[test]
+    fn test_optionals_deduplicate_in_hash_set() {
+        let tag = |id, name: Option<&str>| TagOptional { id, name: name.map(str::to_string), color: None };
+        let tags: HashSet<TagOptional> = [tag(1, Some("a")), tag(1, Some("a")), tag(1, None), tag(2, Some("a"))].into_iter().collect();
+        assert_eq!(tags.len(), 3);
+        assert!(tags.contains(&tag(1, None)));
+    }
+}
+
+mod sea_orm_default_values {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub active: sea_orm::ActiveValue<bool>,
+        pub tags: sea_orm::ActiveValue<Vec<String>>,
+        pub note: sea_orm::ActiveValue<Option<String>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_default_values)]
+    pub struct Account {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub active: bool,
+        pub tags: Vec<String>,
+        pub note: Option<String>,
+    }
+
+    #[test]
+    fn test_to_create_active_defaults_unset_fields() {
+        let optional = AccountOptional { id: 1, active: None, tags: Some(vec!["a".to_string()]), note: None };
+        let active = optional.clone().to_create_active();
+        assert_eq!(active.id, sea_orm::ActiveValue::NotSet);
+        assert_eq!(active.active, sea_orm::ActiveValue::Set(false));
+        assert_eq!(active.tags, sea_orm::ActiveValue::Set(vec!["a".to_string()]));
+        assert_eq!(active.note, sea_orm::ActiveValue::Set(None));
+        assert_eq!(optional.to_active().active, sea_orm::ActiveValue::NotSet);
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct TokenActiveModel {
+        pub id: sea_orm::ActiveValue<u128>,
+        pub label: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_default_values, sea_orm_active_model_type = "TokenActiveModel")]
+    pub struct Token {
+        #[optionalize(sea_orm_primary_key, sea_orm_insert_id)]
+        pub id: u128,
+        pub label: String,
+    }
+
+    #[test]
+    fn test_insert_id_is_set_on_create() {
+        let optional = TokenOptional { id: 42, label: None };
+        assert_eq!(optional.clone().to_create_active().id, sea_orm::ActiveValue::Set(42));
+        assert_eq!(optional.to_active().id, sea_orm::ActiveValue::Unchanged(42));
+    }
+}
+
+mod newtype_wrap {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, newtype_wrap = "ProfileUpdate")]
+    pub struct Profile {
+        pub name: String,
+        pub age: u8,
+    }
+
+    fn name_of(update: &ProfileUpdate) -> Option<&str> {
+        update.name.as_deref()
+    }
+
+    #[test]
+    fn test_newtype_wraps_optional() {
+        let update = ProfileUpdate::from(ProfileOptional { name: Some("Ada".to_string()), age: None });
+        assert_eq!(name_of(&update), Some("Ada"));
+        assert_eq!(update.age, None);
+        assert_eq!(update.0.name, Some("Ada".to_string()));
+    }
+}
+
+mod sea_orm_soft_delete {
+    use super::{chrono, sea_orm};
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub title: sea_orm::ActiveValue<String>,
+        pub deleted_at: sea_orm::ActiveValue<Option<chrono::NaiveDateTime>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_soft_delete = "deleted_at", sea_orm_soft_undelete = "deleted_at")]
+    pub struct Post {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub title: String,
+        pub deleted_at: Option<chrono::NaiveDateTime>,
+    }
+
+    #[test]
+    fn test_soft_delete_only_sets_deleted_at() {
+        let optional = PostOptional { id: 4, title: Some("draft".to_string()), deleted_at: None };
+        let mut model = optional.clone().to_active();
+        assert_eq!(model.title, sea_orm::ActiveValue::Set("draft".to_string()));
+        optional.soft_delete(&mut model);
+        assert_eq!(model.id, sea_orm::ActiveValue::Unchanged(4));
+        assert_eq!(model.title, sea_orm::ActiveValue::NotSet);
+        assert_eq!(model.deleted_at, sea_orm::ActiveValue::Set(Some(chrono::Utc::now().naive_utc())));
+    }
+
+    #[test]
+    fn test_soft_undelete_only_clears_deleted_at() {
+        let optional = PostOptional { id: 4, title: Some("draft".to_string()), deleted_at: Some(1) };
+        let mut model = optional.to_active();
+        PostOptional::soft_undelete(&mut model);
+        assert_eq!(model.id, sea_orm::ActiveValue::Unchanged(4));
+        assert_eq!(model.title, sea_orm::ActiveValue::NotSet);
+        assert_eq!(model.deleted_at, sea_orm::ActiveValue::Set(None));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct CommentActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub body: sea_orm::ActiveValue<String>,
+        pub deleted_at: sea_orm::ActiveValue<Option<chrono::NaiveDateTime>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_soft_delete = "deleted_at", sea_orm_active_model_type = "CommentActiveModel")]
+    pub struct Comment {
+        #[optionalize(skip)]
+        pub id: i32,
+        pub body: String,
+        pub deleted_at: Option<chrono::NaiveDateTime>,
+    }
+
+    #[test]
+    fn test_soft_delete_keeps_skipped_key() {
+        let optional = CommentOptional { id: 9, body: Some("hi".to_string()), deleted_at: None };
+        let mut model = optional.clone().to_active();
+        optional.soft_delete(&mut model);
+        assert_eq!(model.id, sea_orm::ActiveValue::Unchanged(9));
+        assert_eq!(model.body, sea_orm::ActiveValue::NotSet);
+    }
+}
+
+#[cfg(feature = "json")]
+mod generic_optional {
+    use crate::{GenericOptional, Optionalize};
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, generic_optional, rename_all = "camelCase")]
+    pub struct Settings {
+        #[optionalize(skip)]
+        pub user_id: u32,
+        pub dark_mode: bool,
+        pub font_size: Option<u8>,
+    }
+
+    #[test]
+    fn test_into_generic_keeps_set_fields() {
+        let generic = SettingsOptional { user_id: 3, dark_mode: Some(true), font_size: None }.into_generic().unwrap();
+        assert_eq!(generic.get("userId"), Some(&serde_json::json!(3)));
+        assert_eq!(generic.get("darkMode"), Some(&serde_json::json!(true)));
+        assert!(!generic.is_set("fontSize"));
+        assert_eq!(generic.fields.len(), 2);
+        assert_eq!(GenericOptional::<serde_json::Value>::default().fields.len(), 0);
+    }
+}
+
+mod prelude {
+    use crate::prelude::*;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, accumulate_errors)]
+    pub struct Item {
+        pub name: String,
+        pub price: u32,
+    }
+
+    #[test]
+    fn test_prelude_brings_generated_names_into_scope() {
+        let missing = Item::try_from(ItemOptional { name: Some("pen".to_string()), price: None });
+        assert_eq!(missing.err(), Some(MissingFieldsError { fields: vec!["price"] }));
+    }
+}
+
+mod deny_extra_fields {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub name: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(deny_extra_fields, compact)]
+    pub struct Team {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[test]
+    fn test_matching_fields_compile() {
+        let optional = TeamOptional { id: 1, name: Some("core".to_string()) };
+        assert_eq!((optional.id, optional.name.as_deref()), (1, Some("core")));
+    }
+}
+
+mod json_value {
+    use super::sea_orm;
+    use crate::Optionalize;
+    use serde_json::{json, Value};
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub metadata: sea_orm::ActiveValue<Value>,
+        pub extra: sea_orm::ActiveValue<Option<serde_json::Value>>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    pub struct Document {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub metadata: Value,
+        pub extra: Option<serde_json::Value>,
+    }
+
+    #[test]
+    fn test_json_values_wrap_once() {
+        let optional: DocumentOptional = serde_json::from_str(r#"{"id": 1, "metadata": {"a": [1, 2]}}"#).unwrap();
+        assert_eq!(optional.metadata, Some(json!({"a": [1, 2]})));
+        assert_eq!(optional.extra, None);
+
+        let active = DocumentOptional { extra: Some(json!("x")), ..optional }.to_active();
+        assert_eq!(active.metadata, sea_orm::ActiveValue::Set(json!({"a": [1, 2]})));
+        assert_eq!(active.extra, sea_orm::ActiveValue::Set(Some(json!("x"))));
+    }
+}
+
+mod sea_orm_column_name {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub user_id: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(deny_extra_fields)]
+    pub struct Session {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        #[optionalize(sea_orm_column_name = "user_id")]
+        pub uid: String,
+    }
+
+    #[test]
+    fn test_to_active_uses_column_name() {
+        let active = SessionOptional { id: 2, uid: Some("u-1".to_string()) }.to_active();
+        assert_eq!(active.id, sea_orm::ActiveValue::Unchanged(2));
+        assert_eq!(active.user_id, sea_orm::ActiveValue::Set("u-1".to_string()));
+    }
+}
+
+mod doc {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, doc = "Request body for PATCH /pages/{id}.", doc = "Unset fields are left unchanged.")]
+    pub struct Page {
+        pub title: String,
+    }
+
+    #[test]
+    fn test_documented_optional_compiles() {
+        assert_eq!(PageOptional::default().title, None);
+    }
+}
+
+mod generate_constants {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, generate_constants)]
+    pub struct Address {
+        pub street: String,
+        pub r#type: String,
+    }
+
+    #[test]
+    fn test_field_name_constants() {
+        assert_eq!(AddressOptional::FIELD_NAME_STREET, "street");
+        assert_eq!(AddressOptional::FIELD_NAME_TYPE, "type");
+        let optional = AddressOptional { street: Some("Main".to_string()), r#type: Some("home".to_string()) };
+        let retained = optional.retain_fields(&[AddressOptional::FIELD_NAME_TYPE]);
+        assert_eq!((retained.street, retained.r#type), (None, Some("home".to_string())));
+    }
+}
+
+#[cfg(feature = "json")]
+mod try_from_json {
+    use crate::Optionalize;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip)]
+    pub struct Patch {
+        pub title: String,
+        pub pages: u32,
+    }
+
+    #[test]
+    fn test_try_from_json_value() {
+        let optional = PatchOptional::try_from(&json!({"title": "Intro", "unknown": true})).unwrap();
+        assert_eq!((optional.title, optional.pages), (Some("Intro".to_string()), None));
+        assert!(PatchOptional::try_from(&json!({"pages": "many"})).is_err());
+    }
+
+    #[test]
+    fn test_try_from_json_value_reports_bad_input() {
+        let wrong_type = PatchOptional::try_from(&json!({"title": "Intro", "pages": -1})).unwrap_err();
+        assert!(wrong_type.to_string().contains("expected u32"), "{}", wrong_type);
+        assert!(PatchOptional::try_from(&json!("Intro")).is_err());
+        let nulls = PatchOptional::try_from(&json!({"title": null, "pages": null})).unwrap();
+        assert_eq!((nulls.title, nulls.pages), (None, None));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, rename_all = "camelCase")]
+    pub struct Chapter {
+        #[optionalize(skip)]
+        pub book_id: u32,
+        pub page_count: u32,
+        pub subtitle: Option<String>,
+    }
+
+    #[test]
+    fn test_into_json_value_keeps_only_set_fields() {
+        let optional = ChapterOptional { book_id: 2, page_count: Some(30), subtitle: None };
+        assert_eq!(serde_json::Value::from(optional.clone()), json!({"bookId": 2, "pageCount": 30}));
+        let value: serde_json::Value = optional.into();
+        assert_eq!(value["pageCount"], json!(30));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip)]
+    pub struct Board {
+        pub title: String,
+        pub cells: std::collections::BTreeMap<(u8, u8), char>,
+    }
+
+    #[test]
+    fn test_into_json_value_writes_unserializable_fields_as_null() {
+        let cells = [((0, 0), 'x')].into_iter().collect();
+        let optional = BoardOptional { title: Some("game".to_string()), cells: Some(cells) };
+        assert_eq!(serde_json::Value::from(optional), json!({"title": "game", "cells": null}));
+    }
+
+    #[derive(Debug, Deserialize, Clone)]
+    pub struct Secret(#[allow(dead_code)] String);
+
+    // Fields that are not `Serialize` only rule out the `From<Optional>` impl.
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip)]
+    pub struct Credentials {
+        pub secret: Secret,
+    }
+}
+
+mod sea_orm_skip_in_active {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub password: sea_orm::ActiveValue<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    pub struct PasswordChange {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub password: String,
+        #[optionalize(sea_orm_skip_in_active)]
+        pub confirm_password: String,
+    }
+
+    #[test]
+    fn test_skipped_field_is_left_out_of_to_active() {
+        let optional: PasswordChangeOptional = serde_json::from_str(r#"{"id": 1, "password": "a", "confirm_password": "a"}"#).unwrap();
+        assert_eq!(optional.confirm_password, Some("a".to_string()));
+        let active = optional.to_active();
+        assert_eq!(active.password, sea_orm::ActiveValue::Set("a".to_string()));
+    }
+}
+
+mod sea_orm_active_model_type {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    pub mod entity {
+        pub mod user {
+            #[allow(dead_code)]
+            #[derive(Debug)]
+            pub struct ActiveModel {
+                pub id: super::super::sea_orm::ActiveValue<i32>,
+                pub name: super::super::sea_orm::ActiveValue<String>,
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_active_model_type = "entity::user::ActiveModel", sea_orm_into_active_model)]
+    pub struct Member {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub name: String,
+    }
+
+    #[test]
+    fn test_explicit_active_model_type() {
+        let active: entity::user::ActiveModel = MemberOptional { id: 1, name: None }.to_active();
+        assert_eq!(active.name, sea_orm::ActiveValue::NotSet);
+        let active: entity::user::ActiveModel = sea_orm::IntoActiveModel::into_active_model(MemberOptional { id: 1, name: None });
+        assert_eq!(active.id, sea_orm::ActiveValue::Unchanged(1));
+    }
+}
+
+mod impl_ord {
+    use crate::Optionalize;
+    use std::collections::BTreeSet;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, impl_ord, impl_hash)]
+    pub struct Version {
+        pub major: u32,
+        pub minor: u32,
+    }
+
+    #[test]
+    fn test_optionals_sort_in_b_tree_set() {
+        let version = |major, minor| VersionOptional { major, minor };
+        let versions: BTreeSet<_> = [version(Some(1), None), version(None, Some(3)), version(Some(1), Some(2)), version(None, Some(3))].into_iter().collect();
+        assert_eq!(versions.into_iter().collect::<Vec<_>>(), vec![version(None, Some(3)), version(Some(1), None), version(Some(1), Some(2))]);
+    }
+}
+
+mod impl_snapshot {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, impl_snapshot)]
+    pub struct Balance {
+        #[optionalize(skip)]
+        pub account: u32,
+        pub amount: i64,
+        pub memo: Option<String>,
+    }
+
+    #[test]
+    fn test_snapshot_clones_every_field() {
+        let balance = Balance { account: 7, amount: 100, memo: None };
+        let snapshot = BalanceOptional::snapshot(&balance);
+        assert_eq!((snapshot.account, snapshot.amount, snapshot.memo), (7, Some(100), None));
+        assert_eq!(balance.amount, 100);
+    }
+}
+
+mod custom_deserializer {
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip, custom_deserializer, rename_all = "camelCase")]
+    pub struct Reading {
+        #[optionalize(skip)]
+        pub sensor_id: u32,
+        pub celsius: f32,
+        pub label: Option<String>,
+    }
+
+    #[test]
+    fn test_custom_deserializer_reads_maps() {
+        let reading: ReadingOptional = serde_json::from_str(r#"{"sensorId": 3, "celsius": 1.5, "celsius": 2.5, "extra": true}"#).unwrap();
+        assert_eq!((reading.sensor_id, reading.celsius, reading.label), (3, Some(2.5), None));
+        assert!(serde_json::from_str::<ReadingOptional>(r#"{"celsius": 1.5}"#).is_err());
+    }
+
+    #[test]
+    fn test_custom_deserializer_reads_sequences() {
+        let reading: ReadingOptional = serde_json::from_str(r#"[3, null, "roof"]"#).unwrap();
+        assert_eq!((reading.sensor_id, reading.celsius, reading.label), (3, None, Some("roof".to_string())));
+        assert!(serde_json::from_str::<ReadingOptional>("[3]").is_err());
+    }
+}
+
+mod legacy_ignore {
+    // `#[optionalize_ignore]` still works, but every use warns.
+    #![allow(deprecated)]
+
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Optionalize)]
+    #[optionalize(sea_orm_skip)]
+    pub struct Tag {
+        #[optionalize_ignore]
+        pub id: u32,
+        #[optionalize(skip)]
+        pub slug: String,
+        pub label: String,
+    }
+
+    #[test]
+    fn test_ignore_and_skip_keep_their_types() {
+        let tag = TagOptional { id: 1, slug: "rust".to_string(), label: None };
+        assert_eq!((tag.id, tag.slug.as_str(), tag.label), (1, "rust", None));
+    }
+}
+
+mod exclude {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub title: sea_orm::ActiveValue<String>,
+        pub created_at: sea_orm::ActiveValue<u64>,
+        pub updated_at: sea_orm::ActiveValue<u64>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq, Clone)]
+    #[optionalize(exclude = "created_at, updated_at", clone_on_none)]
+    pub struct Article {
+        #[optionalize(sea_orm_primary_key)]
+        pub id: i32,
+        pub title: String,
+        pub created_at: u64,
+        pub updated_at: u64,
+    }
+
+    #[test]
+    fn test_excluded_fields_are_absent() {
+        let optional = ArticleOptional { id: 1, title: Some("new".to_string()) };
+        let active = optional.clone().to_active();
+        assert_eq!(active.title, sea_orm::ActiveValue::Set("new".to_string()));
+        assert_eq!(active.created_at, sea_orm::ActiveValue::NotSet);
+        assert_eq!(active.updated_at, sea_orm::ActiveValue::NotSet);
+
+        let base = Article { id: 1, title: "old".to_string(), created_at: 10, updated_at: 20 };
+        let patched = Article { title: "new".to_string(), ..base.clone() };
+        assert_eq!(optional.patch_ref(&base), patched);
+        assert_eq!(optional.patch(base), patched);
+    }
+}
+
+mod include {
+    use super::sea_orm;
+    use crate::Optionalize;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    pub struct ActiveModel {
+        pub id: sea_orm::ActiveValue<i32>,
+        pub name: sea_orm::ActiveValue<String>,
+        pub bio: sea_orm::ActiveValue<Option<String>>,
+        pub karma: sea_orm::ActiveValue<i64>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Optionalize, Debug, PartialEq)]
+    #[optionalize(include = "name, bio")]
+    pub struct Profile {
+        pub id: i32,
+        pub name: String,
+        pub bio: Option<String>,
+        pub karma: i64,
+    }
+
+    #[test]
+    fn test_only_included_fields_are_kept() {
+        let optional = ProfileOptional { name: None, bio: Some("hi".to_string()) };
+        let active = optional.clone().to_active();
+        assert_eq!(active.id, sea_orm::ActiveValue::NotSet);
+        assert_eq!(active.name, sea_orm::ActiveValue::NotSet);
+        assert_eq!(active.bio, sea_orm::ActiveValue::Set(Some("hi".to_string())));
+        assert_eq!(active.karma, sea_orm::ActiveValue::NotSet);
+
+        let base = Profile { id: 3, name: "Ada".to_string(), bio: None, karma: 9 };
+        let patched = optional.patch(base);
+        assert_eq!(patched, Profile { id: 3, name: "Ada".to_string(), bio: Some("hi".to_string()), karma: 9 });
+    }
+}